@@ -0,0 +1,27 @@
+#[macro_use]
+extern crate bencher;
+extern crate handy_async;
+
+use bencher::Bencher;
+use handy_async::io::ReadFrom;
+use handy_async::pattern::Endian;
+use handy_async::pattern::read::{Fixed, U16, U32};
+
+const INPUT: [u8; 12] = [0, 1, 0, 2, 0, 0, 0, 3, 0, 0, 0, 4];
+
+fn read_fields(b: &mut Bencher) {
+    b.iter(|| {
+        let pattern = (U16.be(), U16.be(), U32.be(), U32.be());
+        pattern.sync_read_from(&INPUT[..]).unwrap()
+    });
+}
+
+fn read_fixed(b: &mut Bencher) {
+    b.iter(|| {
+        let pattern = Fixed((U16.be(), U16.be(), U32.be(), U32.be()));
+        pattern.sync_read_from(&INPUT[..]).unwrap()
+    });
+}
+
+benchmark_group!(benches, read_fields, read_fixed);
+benchmark_main!(benches);
@@ -0,0 +1,153 @@
+//! Checksum algorithms used by the `Pattern::checksummed` combinator.
+//!
+//! # Examples
+//!
+//! ```
+//! use handy_async::checksum::{Checksum, Crc32, Crc16Ccitt, Adler32, InternetChecksum};
+//!
+//! assert_eq!(Crc32::of(b"123456789"), 0xCBF4_3926);
+//! assert_eq!(Crc16Ccitt::of(b"123456789"), 0x29B1);
+//! assert_eq!(Adler32::of(b"Wikipedia"), 0x11E6_0398);
+//! assert_eq!(InternetChecksum::of(&[0x45, 0x00, 0x00, 0x1c]), 0xBAE3);
+//! ```
+
+/// A trait for checksum (or digest) algorithms.
+///
+/// A checksum is computed incrementally by feeding bytes with `update`.
+pub trait Checksum: Default {
+    /// The type of the computed checksum.
+    type Digest;
+
+    /// Feeds `bytes` to this checksum.
+    fn update(&mut self, bytes: &[u8]);
+
+    /// Returns the checksum of the bytes fed so far.
+    fn digest(&self) -> Self::Digest;
+
+    /// Computes the checksum of `bytes`.
+    fn of(bytes: &[u8]) -> Self::Digest {
+        let mut this = Self::default();
+        this.update(bytes);
+        this.digest()
+    }
+}
+
+const CRC32_TABLE: [u32; 256] = crc32_table();
+
+const fn crc32_table() -> [u32; 256] {
+    let mut table = [0; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut c = i as u32;
+        let mut k = 0;
+        while k < 8 {
+            c = if c & 1 == 1 { 0xEDB8_8320 ^ (c >> 1) } else { c >> 1 };
+            k += 1;
+        }
+        table[i] = c;
+        i += 1;
+    }
+    table
+}
+
+/// CRC-32 (IEEE 802.3) checksum.
+#[derive(Debug, Clone)]
+pub struct Crc32(u32);
+impl Default for Crc32 {
+    fn default() -> Self {
+        Crc32(0xFFFF_FFFF)
+    }
+}
+impl Checksum for Crc32 {
+    type Digest = u32;
+    fn update(&mut self, bytes: &[u8]) {
+        for &b in bytes {
+            self.0 = CRC32_TABLE[((self.0 ^ u32::from(b)) & 0xFF) as usize] ^ (self.0 >> 8);
+        }
+    }
+    fn digest(&self) -> Self::Digest {
+        !self.0
+    }
+}
+
+/// CRC-16/CCITT checksum (polynomial `0x1021` and initial value `0xFFFF`,
+/// also known as CRC-16/CCITT-FALSE).
+#[derive(Debug, Clone)]
+pub struct Crc16Ccitt(u16);
+impl Default for Crc16Ccitt {
+    fn default() -> Self {
+        Crc16Ccitt(0xFFFF)
+    }
+}
+impl Checksum for Crc16Ccitt {
+    type Digest = u16;
+    fn update(&mut self, bytes: &[u8]) {
+        for &b in bytes {
+            self.0 ^= u16::from(b) << 8;
+            for _ in 0..8 {
+                self.0 = if self.0 & 0x8000 != 0 {
+                    (self.0 << 1) ^ 0x1021
+                } else {
+                    self.0 << 1
+                };
+            }
+        }
+    }
+    fn digest(&self) -> Self::Digest {
+        self.0
+    }
+}
+
+/// Adler-32 checksum.
+#[derive(Debug, Clone)]
+pub struct Adler32 {
+    a: u32,
+    b: u32,
+}
+impl Default for Adler32 {
+    fn default() -> Self {
+        Adler32 { a: 1, b: 0 }
+    }
+}
+impl Checksum for Adler32 {
+    type Digest = u32;
+    fn update(&mut self, bytes: &[u8]) {
+        const MOD: u32 = 65_521;
+        for &b in bytes {
+            self.a = (self.a + u32::from(b)) % MOD;
+            self.b = (self.b + self.a) % MOD;
+        }
+    }
+    fn digest(&self) -> Self::Digest {
+        (self.b << 16) | self.a
+    }
+}
+
+/// Internet checksum (RFC 1071) used by IPv4, TCP and UDP.
+///
+/// The bytes are summed as big-endian 16-bit words.
+/// If the total length is odd, the last byte is padded with zero.
+#[derive(Debug, Clone, Default)]
+pub struct InternetChecksum {
+    sum: u64,
+    odd_byte: Option<u8>,
+}
+impl Checksum for InternetChecksum {
+    type Digest = u16;
+    fn update(&mut self, bytes: &[u8]) {
+        for &b in bytes {
+            if let Some(high) = self.odd_byte.take() {
+                self.sum += u64::from(u16::from(high) << 8 | u16::from(b));
+            } else {
+                self.odd_byte = Some(b);
+            }
+        }
+    }
+    fn digest(&self) -> Self::Digest {
+        let mut sum = self.sum + self.odd_byte.map_or(0, |b| u64::from(b) << 8);
+        while sum > 0xFFFF {
+            sum = (sum & 0xFFFF) + (sum >> 16);
+        }
+        !(sum as u16)
+    }
+}
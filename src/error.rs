@@ -1,6 +1,7 @@
 //! Error related components.
 use std::fmt;
 use std::error;
+use std::io;
 
 /// The error type for asynchronous operations.
 ///
@@ -25,6 +26,16 @@ use std::error;
 pub struct AsyncError<T, E> {
     state: T,
     error: E,
+    context: Option<Box<Context>>,
+}
+
+/// The location information attached to an `AsyncError`.
+///
+/// This is allocated only when the location is known.
+#[derive(Debug, Default)]
+struct Context {
+    offset: Option<u64>,
+    path: Vec<&'static str>,
 }
 impl<T, E> AsyncError<T, E>
 where
@@ -35,6 +46,7 @@ where
         AsyncError {
             state: state,
             error: error,
+            context: None,
         }
     }
 
@@ -73,6 +85,61 @@ where
         (self.state, self.error)
     }
 
+    /// Returns the absolute byte offset at which this error was detected, if it is known.
+    ///
+    /// This is the position of the matcher at the time the error occurred
+    /// (i.e., the number of bytes consumed so far, excluding bytes pushed back by lookahead).
+    /// Labelled patterns record it for matchers which track their position,
+    /// and `read_from` and `write_into` record it for the other errors.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate futures;
+    /// # extern crate handy_async;
+    /// use futures::Future;
+    /// use handy_async::io::ReadFrom;
+    /// use handy_async::pattern::{Pattern, Endian};
+    /// use handy_async::pattern::read::{U8, U16};
+    ///
+    /// # fn main() {
+    /// let pattern = (U8, U16.be().label("length"), U8.expect_eq(0).label("flags")).label("header");
+    /// let e = pattern.read_from(&[1, 0, 2, 7][..]).wait().err().unwrap();
+    /// assert_eq!(e.offset(), Some(4));
+    /// assert_eq!(e.path(), ["header", "flags"]);
+    /// assert_eq!(e.to_string(),
+    ///            "Asynchronous Error: Unexpected value: 7 (expected 0) \
+    ///             (at byte offset 4 in header.flags)");
+    /// # }
+    /// ```
+    pub fn offset(&self) -> Option<u64> {
+        self.context.as_ref().and_then(|c| c.offset)
+    }
+
+    /// Returns the labels of the patterns in which this error occurred.
+    ///
+    /// The outermost label comes first.
+    /// Labels are attached via the [`Pattern::label`](../pattern/trait.Pattern.html#method.label)
+    /// method.
+    pub fn path(&self) -> Vec<&'static str> {
+        self.context.as_ref().map_or_else(Vec::new, |c| {
+            c.path.iter().rev().cloned().collect()
+        })
+    }
+
+    /// Sets the absolute byte offset at which this error occurred.
+    pub fn with_offset(mut self, offset: u64) -> Self {
+        self.context.get_or_insert_with(Box::default).offset = Some(offset);
+        self
+    }
+
+    /// Adds `label` to the path of this error as the new outermost label.
+    pub fn with_label(mut self, label: &'static str) -> Self {
+        // Labels are pushed from the innermost one, and reversed by `path`.
+        self.context.get_or_insert_with(Box::default).path.push(label);
+        self
+    }
+
     /// Maps a `AsyncError<T, E>` to `AsyncError<U, E>` by
     /// applying a function `F` to the contained state.
     ///
@@ -94,6 +161,30 @@ where
         AsyncError {
             state: f(self.state),
             error: self.error,
+            context: self.context,
+        }
+    }
+
+    /// Maps a `AsyncError<T, E>` to `AsyncError<T, U>` by
+    /// applying a function `F` to the contained error.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::io::{Error, ErrorKind};
+    /// use handy_async::error::AsyncError;
+    ///
+    /// let error = AsyncError::new("dummy_state", Error::new(ErrorKind::Other, "foo"));
+    /// let error = error.map_error(|e| Error::new(ErrorKind::InvalidData, e));
+    /// assert_eq!(error.error_ref().kind(), ErrorKind::InvalidData);
+    /// ```
+    pub fn map_error<F, U>(self, f: F) -> AsyncError<T, U>
+    where
+        F: FnOnce(E) -> U,
+    {
+        AsyncError {
+            state: self.state,
+            error: f(self.error),
+            context: self.context,
         }
     }
 }
@@ -102,7 +193,13 @@ where
     E: error::Error,
 {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "AsyncError {{ state: _, error: {:?} }}", self.error)
+        write!(
+            f,
+            "AsyncError {{ state: _, error: {:?}, offset: {:?}, path: {:?} }}",
+            self.error,
+            self.offset(),
+            self.path()
+        )
     }
 }
 impl<T, E> fmt::Display for AsyncError<T, E>
@@ -110,7 +207,16 @@ where
     E: error::Error,
 {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "Asynchronous Error: {}", self.error)
+        write!(f, "Asynchronous Error: {}", self.error)?;
+        let path = self.path();
+        match (self.offset(), path.is_empty()) {
+            (Some(offset), true) => write!(f, " (at byte offset {})", offset),
+            (Some(offset), false) => {
+                write!(f, " (at byte offset {} in {})", offset, path.join("."))
+            }
+            (None, false) => write!(f, " (in {})", path.join(".")),
+            (None, true) => Ok(()),
+        }
     }
 }
 impl<T, E> error::Error for AsyncError<T, E>
@@ -124,3 +230,35 @@ where
         self.error.cause()
     }
 }
+
+/// A trait for errors which can distinguish the mismatch of a pattern from other failures.
+///
+/// This is used by the combinators which stop repeating a pattern when it does not match
+/// (e.g., `Pattern::many`), so that the other failures like I/O errors are not swallowed.
+///
+/// If you use a matcher with your own error type, implement this trait for that type
+/// to use such combinators.
+pub trait MatchError {
+    /// Returns `true` if this error indicates that the input did not match the pattern.
+    fn is_mismatch(&self) -> bool;
+
+    /// Returns `true` if this error is caused by an unexpected "End-Of-Stream".
+    ///
+    /// The default implementation returns `false`.
+    fn is_eos(&self) -> bool {
+        false
+    }
+}
+impl MatchError for io::Error {
+    /// The errors of the `InvalidData`, `InvalidInput` and `UnexpectedEof` kinds
+    /// are regarded as mismatches.
+    fn is_mismatch(&self) -> bool {
+        matches!(self.kind(),
+                 io::ErrorKind::InvalidData |
+                 io::ErrorKind::InvalidInput |
+                 io::ErrorKind::UnexpectedEof)
+    }
+    fn is_eos(&self) -> bool {
+        self.kind() == io::ErrorKind::UnexpectedEof
+    }
+}
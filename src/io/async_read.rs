@@ -1,4 +1,5 @@
-use std::io::{Read, Error, ErrorKind};
+use std::cmp;
+use std::io::{Read, Write, Error, ErrorKind};
 use futures::{Poll, Async, Future};
 
 use pattern::Window;
@@ -84,6 +85,88 @@ pub trait AsyncRead: Read + Sized {
     fn async_read_exact<B: AsMut<[u8]>>(self, buf: B) -> ReadExact<Self, B> {
         ReadExact(self.async_read_non_empty(Window::new_mut(buf)))
     }
+
+    /// Creates a future which will read all bytes until EOF asynchronously.
+    ///
+    /// If the stream has more than `max` bytes, the future fails with the `InvalidData` error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate futures;
+    /// # extern crate handy_async;
+    /// use std::io::ErrorKind;
+    /// use futures::Future;
+    /// use handy_async::io::AsyncRead;
+    ///
+    /// # fn main() {
+    /// let (_, buf) = b"hello".async_read_to_end(5).wait().ok().unwrap();
+    /// assert_eq!(buf, b"hello");
+    ///
+    /// let e = b"hello".async_read_to_end(4).wait().err().unwrap();
+    /// assert_eq!(e.error_ref().kind(), ErrorKind::InvalidData);
+    ///
+    /// // `usize::MAX` can be used for reading without limit.
+    /// let (_, buf) = b"hello".async_read_to_end(usize::MAX).wait().ok().unwrap();
+    /// assert_eq!(buf, b"hello");
+    /// # }
+    /// ```
+    fn async_read_to_end(self, max: usize) -> ReadToEnd<Self> {
+        ReadToEnd(Some((self, Vec::new())), max)
+    }
+
+    /// Creates a future which will copy all bytes until EOF to `writer` asynchronously.
+    ///
+    /// `buf_size` is the size of the intermediate buffer.
+    /// The future results in the reader, the writer and the number of copied bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate futures;
+    /// # extern crate handy_async;
+    /// use futures::Future;
+    /// use handy_async::io::AsyncRead;
+    ///
+    /// # fn main() {
+    /// let (_, output, size) = b"hello".async_copy_to(Vec::new(), 2).wait().ok().unwrap();
+    /// assert_eq!(output, b"hello");
+    /// assert_eq!(size, 5);
+    /// # }
+    /// ```
+    fn async_copy_to<W: Write>(self, writer: W, buf_size: usize) -> CopyTo<Self, W> {
+        assert!(buf_size > 0);
+        CopyTo {
+            streams: Some((self, writer)),
+            buf: vec![0; buf_size],
+            start: 0,
+            end: 0,
+            copied: 0,
+        }
+    }
+
+    /// Creates a future which will discard the next `n` bytes asynchronously.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate futures;
+    /// # extern crate handy_async;
+    /// use std::io::ErrorKind;
+    /// use futures::Future;
+    /// use handy_async::io::AsyncRead;
+    ///
+    /// # fn main() {
+    /// let rest = (&b"hello"[..]).async_skip(3).wait().ok().unwrap();
+    /// assert_eq!(rest, b"lo");
+    ///
+    /// let e = b"hello".async_skip(8).wait().err().unwrap();
+    /// assert_eq!(e.error_ref().kind(), ErrorKind::UnexpectedEof);
+    /// # }
+    /// ```
+    fn async_skip(self, n: u64) -> SkipBytes<Self> {
+        SkipBytes(Some(self), n)
+    }
 }
 impl<R: Read> AsyncRead for R {}
 
@@ -197,3 +280,179 @@ where
         Ok(Async::NotReady)
     }
 }
+
+/// A future which will read all bytes from `R` until EOF.
+///
+/// This is created by calling `AsyncRead::async_read_to_end` method.
+#[derive(Debug)]
+pub struct ReadToEnd<R>(Option<(R, Vec<u8>)>, usize);
+impl<R> ReadToEnd<R> {
+    /// Returns the reference to the reader.
+    pub fn reader(&self) -> &R {
+        &self.0.as_ref().expect("ReadToEnd has been consumed").0
+    }
+
+    /// Returns the mutable reference to the reader.
+    pub fn reader_mut(&mut self) -> &mut R {
+        &mut self.0.as_mut().expect("ReadToEnd has been consumed").0
+    }
+}
+impl<R: Read> Future for ReadToEnd<R> {
+    type Item = (R, Vec<u8>);
+    type Error = AsyncIoError<(R, Vec<u8>)>;
+    fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
+        let (mut r, mut buf) = self.0.take().expect("Cannot poll ReadToEnd twice");
+        loop {
+            let len = buf.len();
+            // Reads one more byte than the limit to detect the excess.
+            let chunk = cmp::min(cmp::max(len, 1024), self.1.saturating_sub(len).saturating_add(1));
+            buf.resize(len + chunk, 0);
+            let result = r.read(&mut buf[len..]);
+            buf.truncate(len + *result.as_ref().unwrap_or(&0));
+            match result {
+                Ok(0) => return Ok(Async::Ready((r, buf))),
+                Ok(_) if buf.len() > self.1 => {
+                    let e = Error::new(
+                        ErrorKind::InvalidData,
+                        format!("Read size limit ({} bytes) exceeded", self.1),
+                    );
+                    return Err(AsyncIoError::new((r, buf), e));
+                }
+                Ok(_) => {}
+                Err(e) => {
+                    if e.kind() == ErrorKind::WouldBlock {
+                        self.0 = Some((r, buf));
+                        return Ok(Async::NotReady);
+                    } else {
+                        return Err(AsyncIoError::new((r, buf), e));
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// A future which will copy all bytes from `R` to `W`.
+///
+/// This is created by calling `AsyncRead::async_copy_to` method.
+#[derive(Debug)]
+pub struct CopyTo<R, W> {
+    streams: Option<(R, W)>,
+    buf: Vec<u8>,
+    start: usize,
+    end: usize,
+    copied: u64,
+}
+impl<R, W> CopyTo<R, W> {
+    /// Returns the reference to the reader.
+    pub fn reader(&self) -> &R {
+        &self.streams.as_ref().expect("CopyTo has been consumed").0
+    }
+
+    /// Returns the mutable reference to the reader.
+    pub fn reader_mut(&mut self) -> &mut R {
+        &mut self.streams.as_mut().expect("CopyTo has been consumed").0
+    }
+
+    /// Returns the reference to the writer.
+    pub fn writer(&self) -> &W {
+        &self.streams.as_ref().expect("CopyTo has been consumed").1
+    }
+
+    /// Returns the mutable reference to the writer.
+    pub fn writer_mut(&mut self) -> &mut W {
+        &mut self.streams.as_mut().expect("CopyTo has been consumed").1
+    }
+
+    /// Returns the number of bytes copied so far.
+    pub fn copied_size(&self) -> u64 {
+        self.copied
+    }
+}
+impl<R: Read, W: Write> Future for CopyTo<R, W> {
+    type Item = (R, W, u64);
+    type Error = AsyncIoError<(R, W)>;
+    fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
+        let (mut r, mut w) = self.streams.take().expect("Cannot poll CopyTo twice");
+        loop {
+            let result = if self.start < self.end {
+                w.write(&self.buf[self.start..self.end]).and_then(|size| {
+                    if size == 0 {
+                        Err(Error::new(ErrorKind::WriteZero, "Failed to write whole buffer"))
+                    } else {
+                        self.start += size;
+                        self.copied += size as u64;
+                        Ok(size)
+                    }
+                })
+            } else {
+                self.start = 0;
+                self.end = 0;
+                let result = r.read(&mut self.buf);
+                if let Ok(size) = result {
+                    self.end = size;
+                }
+                result
+            };
+            match result {
+                Ok(0) => return Ok(Async::Ready((r, w, self.copied))),
+                Ok(_) => {}
+                Err(e) => {
+                    if e.kind() == ErrorKind::WouldBlock {
+                        self.streams = Some((r, w));
+                        return Ok(Async::NotReady);
+                    } else {
+                        return Err(AsyncIoError::new((r, w), e));
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// A future which will discard the specified number of bytes from `R`.
+///
+/// This is created by calling `AsyncRead::async_skip` method.
+#[derive(Debug)]
+pub struct SkipBytes<R>(Option<R>, u64);
+impl<R> SkipBytes<R> {
+    /// Returns the reference to the reader.
+    pub fn reader(&self) -> &R {
+        self.0.as_ref().expect("SkipBytes has been consumed")
+    }
+
+    /// Returns the mutable reference to the reader.
+    pub fn reader_mut(&mut self) -> &mut R {
+        self.0.as_mut().expect("SkipBytes has been consumed")
+    }
+}
+impl<R: Read> Future for SkipBytes<R> {
+    type Item = R;
+    type Error = AsyncIoError<R>;
+    fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
+        let mut r = self.0.take().expect("Cannot poll SkipBytes twice");
+        let mut buf = [0; 256];
+        while self.1 > 0 {
+            let size = cmp::min(self.1, buf.len() as u64) as usize;
+            match r.read(&mut buf[..size]) {
+                Ok(0) => {
+                    let e = Error::new(
+                        ErrorKind::UnexpectedEof,
+                        format!("Unexpected Eof ({} bytes are required)", self.1),
+                    );
+                    return Err(AsyncIoError::new(r, e));
+                }
+                Ok(size) => self.1 -= size as u64,
+                Err(e) => {
+                    if e.kind() == ErrorKind::WouldBlock {
+                        self.0 = Some(r);
+                        return Ok(Async::NotReady);
+                    } else {
+                        return Err(AsyncIoError::new(r, e));
+                    }
+                }
+            }
+        }
+        Ok(Async::Ready(r))
+    }
+}
@@ -0,0 +1,65 @@
+use std::io::{self, Result};
+use futures::Future;
+
+use matcher::AsyncMatch;
+use super::PatternWriter;
+use super::misc::Counter;
+
+/// A matcher which writes patterns into a counting sink.
+///
+/// This performs a dry run of writing operations,
+/// so it can be used to calculate the byte size of any write pattern
+/// (including `Map`, `AndThen`, `Option` and so on) without producing output.
+///
+/// See the [`ComputeSize`](./trait.ComputeSize.html) trait for the ordinary usage.
+pub type SizeMatcher = PatternWriter<Counter<io::Sink>>;
+
+impl SizeMatcher {
+    /// Makes new `SizeMatcher` instance.
+    pub fn with_sink() -> Self {
+        PatternWriter::new(Counter::with_sink())
+    }
+
+    /// Returns the total byte size written to this matcher so far.
+    pub fn written_size(&self) -> usize {
+        self.inner_ref().written_size()
+    }
+}
+
+/// The `ComputeSize` trait allows for calculating the byte size of a write pattern
+/// by matching it against a [`SizeMatcher`](./type.SizeMatcher.html).
+///
+/// Unlike [`ExternalSize`](./trait.ExternalSize.html),
+/// this can be applied to arbitrarily composed patterns,
+/// but the pattern is consumed by the calculation.
+///
+/// # Examples
+///
+/// ```
+/// use handy_async::io::{ComputeSize, WriteInto};
+/// use handy_async::pattern::{Pattern, Endian};
+///
+/// let body = (1u8, "Hello".to_string(), Some(2u16.be()));
+/// let size = body.clone().map(|_| ()).compute_size().unwrap();
+/// assert_eq!(size, 8);
+/// assert_eq!(None::<u64>.compute_size().unwrap(), 0);
+///
+/// // Writes the length-prefixed body.
+/// let mut output = Vec::new();
+/// ((size as u32).be(), body).sync_write_into(&mut output).unwrap();
+/// assert_eq!(output, [0, 0, 0, 8, 1, b'H', b'e', b'l', b'l', b'o', 0, 2]);
+/// ```
+pub trait ComputeSize: AsyncMatch<SizeMatcher> {
+    /// Calculates the byte size issued when this pattern is written.
+    fn compute_size(self) -> Result<usize> {
+        self.async_match(SizeMatcher::with_sink())
+            .wait()
+            .map(|(m, _)| m.written_size())
+            .map_err(|e| e.into_error())
+    }
+}
+impl<T> ComputeSize for T
+where
+    T: AsyncMatch<SizeMatcher>,
+{
+}
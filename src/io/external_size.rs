@@ -1,9 +1,11 @@
+use std::collections::VecDeque;
 use std::io::Result;
 
 use pattern;
 use pattern::combinators;
 use pattern::read;
 use pattern::write;
+use super::FixedSize;
 
 /// The `ExternalSize` trait allows for calculating external byte size issued
 /// when an I/O operation is performed on a pattern.
@@ -60,6 +62,24 @@ where
         self.iter_ref().clone().map(|t| t.external_size()).sum()
     }
 }
+impl<I, C> ExternalSize for combinators::IterCollect<I, C>
+where
+    I: Iterator + Clone,
+    I::Item: ExternalSize,
+{
+    fn external_size(&self) -> usize {
+        self.iter_ref().clone().map(|t| t.external_size()).sum()
+    }
+}
+impl<I, F, T> ExternalSize for combinators::IterTryFold<I, F, T>
+where
+    I: Iterator + Clone,
+    I::Item: ExternalSize,
+{
+    fn external_size(&self) -> usize {
+        self.iter_ref().clone().map(|t| t.external_size()).sum()
+    }
+}
 impl<T> ExternalSize for pattern::Window<T> {
     fn external_size(&self) -> usize {
         self.end() - self.start()
@@ -119,133 +139,56 @@ impl ExternalSize for () {
         0
     }
 }
-impl<T0, T1> ExternalSize for (T0, T1)
-where
-    T0: ExternalSize,
-    T1: ExternalSize,
-{
-    fn external_size(&self) -> usize {
-        self.0.external_size() + self.1.external_size()
-    }
-}
-impl<T0, T1, T2> ExternalSize for (T0, T1, T2)
-where
-    T0: ExternalSize,
-    T1: ExternalSize,
-    T2: ExternalSize,
-{
-    fn external_size(&self) -> usize {
-        self.0.external_size() + self.1.external_size() + self.2.external_size()
-    }
-}
-impl<T0, T1, T2, T3> ExternalSize for (T0, T1, T2, T3)
-where
-    T0: ExternalSize,
-    T1: ExternalSize,
-    T2: ExternalSize,
-    T3: ExternalSize,
-{
-    fn external_size(&self) -> usize {
-        self.0.external_size() + self.1.external_size() + self.2.external_size() +
-            self.3.external_size()
-    }
-}
-impl<T0, T1, T2, T3, T4> ExternalSize for (T0, T1, T2, T3, T4)
-where
-    T0: ExternalSize,
-    T1: ExternalSize,
-    T2: ExternalSize,
-    T3: ExternalSize,
-    T4: ExternalSize,
-{
-    fn external_size(&self) -> usize {
-        self.0.external_size() + self.1.external_size() + self.2.external_size() +
-            self.3.external_size() + self.4.external_size()
-    }
-}
-impl<T0, T1, T2, T3, T4, T5> ExternalSize for (T0, T1, T2, T3, T4, T5)
-where
-    T0: ExternalSize,
-    T1: ExternalSize,
-    T2: ExternalSize,
-    T3: ExternalSize,
-    T4: ExternalSize,
-    T5: ExternalSize,
-{
-    fn external_size(&self) -> usize {
-        self.0.external_size() + self.1.external_size() + self.2.external_size() +
-            self.3.external_size() + self.4.external_size() + self.5.external_size()
+macro_rules! impl_tuple_external_size {
+    ($($t:ident, $i:tt),*) => {
+        impl<$($t),*> ExternalSize for ($($t),*)
+        where
+            $($t: ExternalSize),*
+        {
+            fn external_size(&self) -> usize {
+                0 $(+ self.$i.external_size())*
+            }
+        }
     }
 }
-impl<T0, T1, T2, T3, T4, T5, T6> ExternalSize for (T0, T1, T2, T3, T4, T5, T6)
-where
-    T0: ExternalSize,
-    T1: ExternalSize,
-    T2: ExternalSize,
-    T3: ExternalSize,
-    T4: ExternalSize,
-    T5: ExternalSize,
-    T6: ExternalSize,
-{
+impl_tuple_external_size!(T0, 0, T1, 1);
+impl_tuple_external_size!(T0, 0, T1, 1, T2, 2);
+impl_tuple_external_size!(T0, 0, T1, 1, T2, 2, T3, 3);
+impl_tuple_external_size!(T0, 0, T1, 1, T2, 2, T3, 3, T4, 4);
+impl_tuple_external_size!(T0, 0, T1, 1, T2, 2, T3, 3, T4, 4, T5, 5);
+impl_tuple_external_size!(T0, 0, T1, 1, T2, 2, T3, 3, T4, 4, T5, 5, T6, 6);
+impl_tuple_external_size!(T0, 0, T1, 1, T2, 2, T3, 3, T4, 4, T5, 5, T6, 6, T7, 7);
+impl_tuple_external_size!(T0, 0, T1, 1, T2, 2, T3, 3, T4, 4, T5, 5, T6, 6, T7, 7, T8, 8);
+impl_tuple_external_size!(T0, 0, T1, 1, T2, 2, T3, 3, T4, 4, T5, 5, T6, 6, T7, 7, T8, 8, T9, 9);
+impl_tuple_external_size!(T0, 0, T1, 1, T2, 2, T3, 3, T4, 4, T5, 5,
+                          T6, 6, T7, 7, T8, 8, T9, 9, T10, 10);
+impl_tuple_external_size!(T0, 0, T1, 1, T2, 2, T3, 3, T4, 4, T5, 5,
+                          T6, 6, T7, 7, T8, 8, T9, 9, T10, 10, T11, 11);
+impl_tuple_external_size!(T0, 0, T1, 1, T2, 2, T3, 3, T4, 4, T5, 5,
+                          T6, 6, T7, 7, T8, 8, T9, 9, T10, 10, T11, 11,
+                          T12, 12);
+impl_tuple_external_size!(T0, 0, T1, 1, T2, 2, T3, 3, T4, 4, T5, 5,
+                          T6, 6, T7, 7, T8, 8, T9, 9, T10, 10, T11, 11,
+                          T12, 12, T13, 13);
+impl_tuple_external_size!(T0, 0, T1, 1, T2, 2, T3, 3, T4, 4, T5, 5,
+                          T6, 6, T7, 7, T8, 8, T9, 9, T10, 10, T11, 11,
+                          T12, 12, T13, 13, T14, 14);
+impl_tuple_external_size!(T0, 0, T1, 1, T2, 2, T3, 3, T4, 4, T5, 5,
+                          T6, 6, T7, 7, T8, 8, T9, 9, T10, 10, T11, 11,
+                          T12, 12, T13, 13, T14, 14, T15, 15);
+impl<T: ExternalSize, const N: usize> ExternalSize for [T; N] {
     fn external_size(&self) -> usize {
-        self.0.external_size() + self.1.external_size() + self.2.external_size() +
-            self.3.external_size() + self.4.external_size() + self.5.external_size() +
-            self.6.external_size()
+        self.iter().map(|t| t.external_size()).sum()
     }
 }
-impl<T0, T1, T2, T3, T4, T5, T6, T7> ExternalSize for (T0, T1, T2, T3, T4, T5, T6, T7)
-    where T0: ExternalSize,
-          T1: ExternalSize,
-          T2: ExternalSize,
-          T3: ExternalSize,
-          T4: ExternalSize,
-          T5: ExternalSize,
-          T6: ExternalSize,
-          T7: ExternalSize
-{
-    fn external_size(&self) -> usize {
-        self.0.external_size() + self.1.external_size() + self.2.external_size() +
-        self.3.external_size() + self.4.external_size() + self.5.external_size() +
-        self.6.external_size() + self.7.external_size()
-    }
-}
-impl<T0, T1, T2, T3, T4, T5, T6, T7, T8> ExternalSize for (T0, T1, T2, T3, T4, T5, T6, T7, T8)
-    where T0: ExternalSize,
-          T1: ExternalSize,
-          T2: ExternalSize,
-          T3: ExternalSize,
-          T4: ExternalSize,
-          T5: ExternalSize,
-          T6: ExternalSize,
-          T7: ExternalSize,
-          T8: ExternalSize
-{
+impl<T: ExternalSize> ExternalSize for VecDeque<T> {
     fn external_size(&self) -> usize {
-        self.0.external_size() + self.1.external_size() + self.2.external_size() +
-        self.3.external_size() + self.4.external_size() + self.5.external_size() +
-        self.6.external_size() + self.7.external_size() + self.8.external_size()
+        self.iter().map(|t| t.external_size()).sum()
     }
 }
-impl<T0, T1, T2, T3, T4, T5, T6, T7, T8, T9> ExternalSize
-    for (T0, T1, T2, T3, T4, T5, T6, T7, T8, T9)
-where
-    T0: ExternalSize,
-    T1: ExternalSize,
-    T2: ExternalSize,
-    T3: ExternalSize,
-    T4: ExternalSize,
-    T5: ExternalSize,
-    T6: ExternalSize,
-    T7: ExternalSize,
-    T8: ExternalSize,
-    T9: ExternalSize,
-{
+impl<T: ExternalSize> ExternalSize for pattern::Seq<T> {
     fn external_size(&self) -> usize {
-        self.0.external_size() + self.1.external_size() + self.2.external_size() +
-            self.3.external_size() + self.4.external_size() + self.5.external_size() +
-            self.6.external_size() + self.7.external_size() +
-            self.8.external_size() +
-            self.9.external_size()
+        self.0.iter().map(|t| t.external_size()).sum()
     }
 }
 impl ExternalSize for write::Flush {
@@ -333,6 +276,31 @@ impl ExternalSize for i64 {
         8
     }
 }
+impl ExternalSize for write::Fill {
+    fn external_size(&self) -> usize {
+        self.1
+    }
+}
+impl ExternalSize for write::Position {
+    fn external_size(&self) -> usize {
+        0
+    }
+}
+impl ExternalSize for read::Position {
+    fn external_size(&self) -> usize {
+        0
+    }
+}
+impl<P: FixedSize> ExternalSize for read::Fixed<P> {
+    fn external_size(&self) -> usize {
+        P::SIZE
+    }
+}
+impl ExternalSize for read::Skip {
+    fn external_size(&self) -> usize {
+        self.0
+    }
+}
 impl ExternalSize for read::U8 {
     fn external_size(&self) -> usize {
         1
@@ -0,0 +1,103 @@
+use std::array;
+
+use pattern::Pattern;
+use pattern::read;
+
+/// The `FixedSize` trait is implemented by reading patterns whose byte size is statically known.
+///
+/// The value of such a pattern can be decoded from a byte slice,
+/// so it can be read by a single read operation via the [`read::Fixed`] pattern.
+///
+/// [`read::Fixed`]: ../pattern/read/struct.Fixed.html
+///
+/// This trait is implemented for all fixnum patterns (including `BE` and `LE` variants),
+/// and arrays and tuples of `FixedSize` patterns.
+///
+/// # Examples
+///
+/// ```
+/// use handy_async::io::FixedSize;
+/// use handy_async::pattern::Endian;
+/// use handy_async::pattern::read::{U8, U16, U32};
+///
+/// type Header = (U8, [U16; 2], U32);
+/// assert_eq!(<Header as FixedSize>::SIZE, 9);
+///
+/// let header = (U8, [U16.be(), U16.be()], U32.le());
+/// assert_eq!(header.decode(&[1, 0, 2, 0, 3, 4, 0, 0, 0]), (1, [2, 3], 4));
+/// ```
+pub trait FixedSize: Pattern {
+    /// The byte size of this pattern.
+    const SIZE: usize;
+
+    /// Decodes the value of this pattern from `buf`.
+    ///
+    /// # Panics
+    ///
+    /// This panics if the length of `buf` is less than `Self::SIZE`.
+    fn decode(self, buf: &[u8]) -> Self::Value;
+}
+
+impl<P: FixedSize, const N: usize> FixedSize for [P; N] {
+    const SIZE: usize = P::SIZE * N;
+    fn decode(self, buf: &[u8]) -> Self::Value {
+        let mut patterns = IntoIterator::into_iter(self);
+        let mut buf = buf;
+        array::from_fn(|_| {
+            let pattern = patterns.next().expect("Never fails");
+            pattern.decode(split_off(&mut buf, P::SIZE))
+        })
+    }
+}
+
+fn split_off<'a>(buf: &mut &'a [u8], size: usize) -> &'a [u8] {
+    let (head, tail) = buf.split_at(size);
+    *buf = tail;
+    head
+}
+
+macro_rules! impl_tuple_fixed_size {
+    ($($t:ident, $i:tt),*) => {
+        impl<$($t),*> FixedSize for ($($t),*)
+        where
+            $($t: FixedSize),*
+        {
+            const SIZE: usize = 0 $(+ $t::SIZE)*;
+            fn decode(self, mut buf: &[u8]) -> Self::Value {
+                ($(self.$i.decode(split_off(&mut buf, $t::SIZE))),*)
+            }
+        }
+    }
+}
+impl_tuple_fixed_size!(T0, 0, T1, 1);
+impl_tuple_fixed_size!(T0, 0, T1, 1, T2, 2);
+impl_tuple_fixed_size!(T0, 0, T1, 1, T2, 2, T3, 3);
+impl_tuple_fixed_size!(T0, 0, T1, 1, T2, 2, T3, 3, T4, 4);
+impl_tuple_fixed_size!(T0, 0, T1, 1, T2, 2, T3, 3, T4, 4, T5, 5);
+impl_tuple_fixed_size!(T0, 0, T1, 1, T2, 2, T3, 3, T4, 4, T5, 5, T6, 6);
+impl_tuple_fixed_size!(T0, 0, T1, 1, T2, 2, T3, 3, T4, 4, T5, 5, T6, 6, T7, 7);
+impl_tuple_fixed_size!(T0, 0, T1, 1, T2, 2, T3, 3, T4, 4, T5, 5, T6, 6, T7, 7, T8, 8);
+impl_tuple_fixed_size!(T0, 0, T1, 1, T2, 2, T3, 3, T4, 4, T5, 5, T6, 6, T7, 7, T8, 8, T9, 9);
+impl_tuple_fixed_size!(T0, 0, T1, 1, T2, 2, T3, 3, T4, 4, T5, 5,
+                       T6, 6, T7, 7, T8, 8, T9, 9, T10, 10);
+impl_tuple_fixed_size!(T0, 0, T1, 1, T2, 2, T3, 3, T4, 4, T5, 5,
+                       T6, 6, T7, 7, T8, 8, T9, 9, T10, 10, T11, 11);
+impl_tuple_fixed_size!(T0, 0, T1, 1, T2, 2, T3, 3, T4, 4, T5, 5,
+                       T6, 6, T7, 7, T8, 8, T9, 9, T10, 10, T11, 11,
+                       T12, 12);
+impl_tuple_fixed_size!(T0, 0, T1, 1, T2, 2, T3, 3, T4, 4, T5, 5,
+                       T6, 6, T7, 7, T8, 8, T9, 9, T10, 10, T11, 11,
+                       T12, 12, T13, 13);
+impl_tuple_fixed_size!(T0, 0, T1, 1, T2, 2, T3, 3, T4, 4, T5, 5,
+                       T6, 6, T7, 7, T8, 8, T9, 9, T10, 10, T11, 11,
+                       T12, 12, T13, 13, T14, 14);
+impl_tuple_fixed_size!(T0, 0, T1, 1, T2, 2, T3, 3, T4, 4, T5, 5,
+                       T6, 6, T7, 7, T8, 8, T9, 9, T10, 10, T11, 11,
+                       T12, 12, T13, 13, T14, 14, T15, 15);
+
+impl<P: FixedSize> FixedSize for read::Fixed<P> {
+    const SIZE: usize = P::SIZE;
+    fn decode(self, buf: &[u8]) -> Self::Value {
+        self.0.decode(buf)
+    }
+}
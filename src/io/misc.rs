@@ -1,5 +1,9 @@
 //! Miscellaneous I/O components.
+use std::cmp;
+use std::error;
+use std::fmt;
 use std::io::{self, Read, Write, Result};
+use futures::task;
 
 /// `Counter` counts the number of read/write bytes issued to an underlying stream.
 ///
@@ -79,3 +83,518 @@ impl<T: Write> Write for Counter<T> {
         self.inner.flush()
     }
 }
+
+/// `ChunkedReader` delivers the bytes of the underlying reader in small chunks.
+///
+/// This is useful to test that a pattern handles short reads correctly.
+///
+/// # Examples
+///
+/// ```
+/// use std::io::Read;
+/// use handy_async::io::misc::ChunkedReader;
+///
+/// let mut reader = ChunkedReader::with_chunk_sizes(&b"Hello World!"[..], vec![1, 3]);
+/// let mut buf = [0; 8];
+/// assert_eq!(reader.read(&mut buf).unwrap(), 1);
+/// assert_eq!(reader.read(&mut buf).unwrap(), 3);
+/// assert_eq!(reader.read(&mut buf).unwrap(), 1);
+/// ```
+#[derive(Debug)]
+pub struct ChunkedReader<R> {
+    inner: R,
+    chunk_sizes: Vec<usize>,
+    next: usize,
+}
+impl<R> ChunkedReader<R> {
+    /// Makes a new `ChunkedReader` which delivers at most `chunk_size` bytes per read.
+    pub fn new(inner: R, chunk_size: usize) -> Self {
+        Self::with_chunk_sizes(inner, vec![chunk_size])
+    }
+
+    /// Makes a new `ChunkedReader` which cycles through `chunk_sizes` as
+    /// the maximum number of bytes delivered per read.
+    ///
+    /// # Panics
+    ///
+    /// `chunk_sizes` must be non empty and must not contain `0`.
+    pub fn with_chunk_sizes(inner: R, chunk_sizes: Vec<usize>) -> Self {
+        assert!(!chunk_sizes.is_empty());
+        assert!(chunk_sizes.iter().all(|&n| n > 0));
+        ChunkedReader {
+            inner,
+            chunk_sizes,
+            next: 0,
+        }
+    }
+
+    /// Gets a reference to the underlying reader.
+    pub fn inner_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying reader.
+    pub fn inner_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Unwraps this `ChunkedReader`, returning the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+impl<R: Read> Read for ChunkedReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        let size = cmp::min(buf.len(), self.chunk_sizes[self.next]);
+        self.next = (self.next + 1) % self.chunk_sizes.len();
+        self.inner.read(&mut buf[..size])
+    }
+}
+
+/// `WouldBlockReader` interleaves the `WouldBlock` error between the reads of
+/// the underlying reader.
+///
+/// Before returning the error, the current task is notified
+/// so that it will be polled again (e.g., by `Future::wait`).
+/// So this reader must be used within a task.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate futures;
+/// # extern crate handy_async;
+/// use futures::Future;
+/// use handy_async::io::ReadFrom;
+/// use handy_async::io::misc::{ChunkedReader, WouldBlockReader};
+/// use handy_async::pattern::Endian;
+/// use handy_async::pattern::read::U32;
+///
+/// # fn main() {
+/// let reader = WouldBlockReader::with_seed(ChunkedReader::new(&[0, 0, 1, 2][..], 1), 7);
+/// let (_, v) = U32.be().read_from(reader).wait().ok().unwrap();
+/// assert_eq!(v, 0x0102);
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct WouldBlockReader<R> {
+    inner: R,
+    schedule: Schedule,
+    reads: u64,
+}
+#[derive(Debug)]
+enum Schedule {
+    Interval(u64),
+    Random(u64),
+}
+impl<R> WouldBlockReader<R> {
+    /// Makes a new `WouldBlockReader` which returns the `WouldBlock` error on
+    /// every `interval`-th read.
+    ///
+    /// # Panics
+    ///
+    /// `interval` must be greater than `1`.
+    pub fn new(inner: R, interval: u64) -> Self {
+        assert!(interval > 1);
+        WouldBlockReader {
+            inner,
+            schedule: Schedule::Interval(interval),
+            reads: 0,
+        }
+    }
+
+    /// Makes a new `WouldBlockReader` which returns the `WouldBlock` error on
+    /// pseudo-random reads determined by `seed`.
+    ///
+    /// Each read fails with the probability of one half.
+    pub fn with_seed(inner: R, seed: u64) -> Self {
+        WouldBlockReader {
+            inner,
+            schedule: Schedule::Random(if seed == 0 { 0x9E37_79B9_7F4A_7C15 } else { seed }),
+            reads: 0,
+        }
+    }
+
+    /// Gets a reference to the underlying reader.
+    pub fn inner_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying reader.
+    pub fn inner_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Unwraps this `WouldBlockReader`, returning the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    fn would_block(&mut self) -> bool {
+        self.reads += 1;
+        match self.schedule {
+            Schedule::Interval(n) => self.reads % n == 0,
+            Schedule::Random(ref mut x) => {
+                // xorshift64*
+                *x ^= *x >> 12;
+                *x ^= *x << 25;
+                *x ^= *x >> 27;
+                x.wrapping_mul(0x2545_F491_4F6C_DD1D) >> 63 == 1
+            }
+        }
+    }
+}
+impl<R: Read> Read for WouldBlockReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        if self.would_block() {
+            task::current().notify();
+            Err(io::Error::new(io::ErrorKind::WouldBlock, "Injected WouldBlock"))
+        } else {
+            self.inner.read(buf)
+        }
+    }
+}
+
+/// `FaultyWriter` fails when the written bytes reach the specified offset.
+///
+/// The write which crosses the offset is shortened to end at the offset,
+/// and the subsequent writes fail with the error kind (`BrokenPipe` by default).
+///
+/// # Examples
+///
+/// ```
+/// use std::io::{ErrorKind, Write};
+/// use handy_async::io::WriteInto;
+/// use handy_async::io::misc::FaultyWriter;
+///
+/// let mut writer = FaultyWriter::new(Vec::new(), 3);
+/// assert_eq!(writer.write(b"Hello").unwrap(), 3);
+/// assert_eq!(writer.write(b"lo").unwrap_err().kind(), ErrorKind::BrokenPipe);
+/// assert_eq!(writer.inner_ref(), b"Hel");
+///
+/// let writer = FaultyWriter::new(Vec::new(), 1).error_kind(ErrorKind::ConnectionReset);
+/// let e = (1u8, 2u8).sync_write_into(writer).unwrap_err();
+/// assert_eq!(e.kind(), ErrorKind::ConnectionReset);
+/// ```
+#[derive(Debug)]
+pub struct FaultyWriter<W> {
+    inner: W,
+    fail_at: u64,
+    written_size: u64,
+    error_kind: io::ErrorKind,
+}
+impl<W> FaultyWriter<W> {
+    /// Makes a new `FaultyWriter` which fails when `fail_at` bytes have been written.
+    pub fn new(inner: W, fail_at: u64) -> Self {
+        FaultyWriter {
+            inner,
+            fail_at,
+            written_size: 0,
+            error_kind: io::ErrorKind::BrokenPipe,
+        }
+    }
+
+    /// Sets the kind of the error to be injected.
+    pub fn error_kind(mut self, kind: io::ErrorKind) -> Self {
+        self.error_kind = kind;
+        self
+    }
+
+    /// Gets a reference to the underlying writer.
+    pub fn inner_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying writer.
+    pub fn inner_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Unwraps this `FaultyWriter`, returning the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+impl<W: Write> Write for FaultyWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        let remaining = self.fail_at - self.written_size;
+        if remaining == 0 && !buf.is_empty() {
+            return Err(io::Error::new(self.error_kind, "Injected fault"));
+        }
+        let size = cmp::min(buf.len() as u64, remaining) as usize;
+        let size = self.inner.write(&buf[..size])?;
+        self.written_size += size as u64;
+        Ok(size)
+    }
+    fn flush(&mut self) -> Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// `Limited` limits the total byte size read from (or written to) an underlying stream.
+///
+/// Once the budget is exhausted, further read and write operations fail
+/// with the `Other` error which wraps `LimitExceeded`,
+/// regardless of which patterns are used on this stream.
+/// The error is not regarded as a mismatch (see `MatchError`),
+/// so combinators such as `many` and `or` do not recover from it.
+/// The bytes read and written share the same budget.
+///
+/// To distinguish the end of the stream from an excess of bytes,
+/// a read operation with the exhausted budget reads one byte from the underlying stream.
+/// If the stream has reached the end, the operation returns `Ok(0)` instead of failing.
+/// Otherwise, the byte is kept and will be returned first after the budget is reset
+/// by `Limited::set_limit`.
+///
+/// # Examples
+///
+/// ```
+/// use std::io::ErrorKind;
+/// use handy_async::io::ReadFrom;
+/// use handy_async::io::misc::{Limited, LimitExceeded};
+/// use handy_async::pattern::Endian;
+/// use handy_async::pattern::read::{All, U8, U16};
+///
+/// let e = All.sync_read_from(Limited::new(&[0; 10][..], 4)).unwrap_err();
+/// assert_eq!(e.kind(), ErrorKind::Other);
+/// assert!(e.get_ref().unwrap().is::<LimitExceeded>());
+///
+/// let mut reader = Limited::new(&[0; 10][..], 4);
+/// let value = (U8, U16.be()).sync_read_from(&mut reader).unwrap();
+/// assert_eq!(value, (0, 0));
+/// assert_eq!(reader.remaining(), 1);
+/// ```
+#[derive(Debug)]
+pub struct Limited<T> {
+    inner: T,
+    limit: u64,
+    remaining: u64,
+    probed: Option<u8>,
+}
+impl<T> Limited<T> {
+    /// Makes a new `Limited` which allows to transfer at most `limit` bytes.
+    pub fn new(inner: T, limit: u64) -> Self {
+        Limited {
+            inner,
+            limit,
+            remaining: limit,
+            probed: None,
+        }
+    }
+
+    /// Gets a reference to the underlying stream.
+    pub fn inner_ref(&self) -> &T {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying stream.
+    pub fn inner_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+
+    /// Unwraps this `Limited`, returning the underlying stream.
+    ///
+    /// Note that the byte which has been read to check the excess is discarded
+    /// (see `Limited::probed_byte`).
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+
+    /// Returns the byte which has been read from the underlying stream
+    /// to check the excess of the budget, but not returned to the caller yet.
+    pub fn probed_byte(&self) -> Option<u8> {
+        self.probed
+    }
+
+    /// Returns the remaining byte size which can be transferred.
+    pub fn remaining(&self) -> u64 {
+        self.remaining
+    }
+
+    /// Resets the budget to `limit` bytes (e.g., for each message).
+    pub fn set_limit(&mut self, limit: u64) {
+        self.limit = limit;
+        self.remaining = limit;
+    }
+
+    fn allowed_size(&self, requested: usize) -> Result<usize> {
+        if self.remaining == 0 && requested > 0 {
+            Err(LimitExceeded { limit: self.limit }.into())
+        } else {
+            Ok(cmp::min(requested as u64, self.remaining) as usize)
+        }
+    }
+}
+impl<T: Read> Read for Limited<T> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        if self.remaining == 0 {
+            if self.probed.is_none() {
+                let mut byte = [0; 1];
+                if self.inner.read(&mut byte)? == 0 {
+                    return Ok(0);
+                }
+                self.probed = Some(byte[0]);
+            }
+            return Err(LimitExceeded { limit: self.limit }.into());
+        }
+        let size = if let Some(b) = self.probed.take() {
+            buf[0] = b;
+            1
+        } else {
+            let size = self.allowed_size(buf.len())?;
+            self.inner.read(&mut buf[..size])?
+        };
+        self.remaining -= size as u64;
+        Ok(size)
+    }
+}
+impl<T: Write> Write for Limited<T> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        let size = self.allowed_size(buf.len())?;
+        let size = self.inner.write(&buf[..size])?;
+        self.remaining -= size as u64;
+        Ok(size)
+    }
+    fn flush(&mut self) -> Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// An error which indicates that the budget of a `Limited` stream has been exhausted.
+#[derive(Debug, Clone)]
+pub struct LimitExceeded {
+    /// The byte size limit of the stream.
+    pub limit: u64,
+}
+impl fmt::Display for LimitExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Byte size limit ({} bytes) exceeded", self.limit)
+    }
+}
+impl error::Error for LimitExceeded {}
+impl From<LimitExceeded> for io::Error {
+    fn from(f: LimitExceeded) -> Self {
+        io::Error::new(io::ErrorKind::Other, f)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::io::{ErrorKind, Read, Write};
+    use super::*;
+
+    #[test]
+    fn patterns_survive_short_reads_and_would_block() {
+        use futures::Future;
+        use io::ReadFrom;
+        use pattern::{Pattern, Endian};
+        use pattern::read::{self, Scan, U8, U16, U32};
+
+        let input = b"\x01\x00\x02\x03\x00\x00\x00foo;1,2,3\r\n";
+        let mut readers: Vec<Box<dyn Read>> = vec![
+            Box::new(ChunkedReader::new(&input[..], 1)),
+            Box::new(ChunkedReader::with_chunk_sizes(&input[..], vec![3, 1, 2])),
+            Box::new(WouldBlockReader::new(ChunkedReader::new(&input[..], 1), 2)),
+            Box::new(WouldBlockReader::new(&input[..], 3)),
+        ];
+        for seed in 0..16 {
+            let reader = WouldBlockReader::with_seed(ChunkedReader::new(&input[..], 1), seed);
+            readers.push(Box::new(reader));
+        }
+        for reader in readers {
+            let pattern = (U8, U16.be(), U32.le());
+            let (rest, header) = pattern.read_from_buffered(reader).wait().ok().unwrap();
+            assert_eq!(header, (1, 2, 3));
+
+            let (rest, name) = Scan::new(b';').read_from_buffered(rest).wait().ok().unwrap();
+            assert_eq!(name, b"foo;");
+
+            let pattern = U8.sep_by(U8.expect_eq(b','));
+            let (rest, items) = pattern.read_from_buffered(rest).wait().ok().unwrap();
+            assert_eq!(items, b"123");
+
+            let until_crlf = read::until(|buf: &[u8], _| {
+                Ok(if buf.ends_with(b"\r\n") { Some(()) } else { None })
+            });
+            let (_, (line, ())) = until_crlf.read_from_buffered(rest).wait().ok().unwrap();
+            assert_eq!(line, b"\r\n");
+        }
+    }
+
+    #[test]
+    fn faulty_writer_fails_patterns_at_every_offset() {
+        use futures::Future;
+        use io::WriteInto;
+        use pattern::Endian;
+
+        for fail_at in 0..7 {
+            let writer = FaultyWriter::new(Vec::new(), fail_at);
+            let e = (1u8, 2u16.be(), 3u32.le()).sync_write_into(writer).unwrap_err();
+            assert_eq!(e.kind(), ErrorKind::BrokenPipe);
+        }
+        let writer = FaultyWriter::new(Vec::new(), 7);
+        let (writer, _) = (1u8, 2u16.be(), 3u32.le()).write_into(writer).wait().ok().unwrap();
+        assert_eq!(writer.inner_ref(), &[1, 0, 2, 3, 0, 0, 0]);
+    }
+
+    #[test]
+    fn limited_reader_allows_exactly_the_limit() {
+        let mut reader = Limited::new(&[0, 1, 2][..], 3);
+        let mut buf = Vec::new();
+        assert_eq!(reader.read_to_end(&mut buf).unwrap(), 3);
+        assert_eq!(buf, [0, 1, 2]);
+        assert_eq!(reader.read(&mut [0; 4]).unwrap(), 0);
+    }
+
+    #[test]
+    fn limited_reader_fails_one_past_the_limit() {
+        let mut reader = Limited::new(&[0, 1, 2, 3][..], 3);
+        let mut buf = [0; 8];
+        assert_eq!(reader.read(&mut buf).unwrap(), 3);
+
+        let e = reader.read(&mut buf).unwrap_err();
+        assert_eq!(e.kind(), ErrorKind::Other);
+        assert!(e.get_ref().unwrap().is::<LimitExceeded>());
+        assert_eq!(reader.probed_byte(), Some(3));
+
+        // The probed byte is returned after the budget is reset.
+        reader.set_limit(3);
+        assert_eq!(reader.read(&mut buf).unwrap(), 1);
+        assert_eq!(buf[0], 3);
+        assert_eq!(reader.read(&mut buf).unwrap(), 0);
+    }
+
+    #[test]
+    fn limited_reader_fails_in_recovering_patterns() {
+        use io::ReadFrom;
+        use pattern::{Pattern, Endian};
+        use pattern::read::{U8, U16, U32};
+
+        let e = U8.many(0, 100).sync_read_from(Limited::new(&[1; 10][..], 4)).unwrap_err();
+        assert!(e.get_ref().unwrap().is::<LimitExceeded>());
+
+        let pattern = U32.be().or(U16.be().map(u32::from));
+        let e = pattern.sync_read_from(Limited::new(&[1; 10][..], 2)).unwrap_err();
+        assert!(e.get_ref().unwrap().is::<LimitExceeded>());
+    }
+
+    #[test]
+    fn limited_writer_allows_exactly_the_limit() {
+        let mut writer = Limited::new(Vec::new(), 3);
+        writer.write_all(&[0, 1, 2]).unwrap();
+        assert_eq!(writer.remaining(), 0);
+        writer.write_all(&[]).unwrap();
+        assert_eq!(writer.into_inner(), [0, 1, 2]);
+    }
+
+    #[test]
+    fn limited_writer_fails_one_past_the_limit() {
+        let mut writer = Limited::new(Vec::new(), 3);
+        let e = writer.write_all(&[0, 1, 2, 3]).unwrap_err();
+        assert_eq!(e.kind(), ErrorKind::Other);
+        assert!(e.get_ref().unwrap().is::<LimitExceeded>());
+        assert_eq!(writer.into_inner(), [0, 1, 2]);
+    }
+}
@@ -1,5 +1,7 @@
 //! I/O operation related components.
+use std::any::Any;
 use std::io;
+use std::error;
 use std::fmt;
 
 pub use self::async_read::AsyncRead;
@@ -7,20 +9,29 @@ pub use self::async_write::AsyncWrite;
 pub use self::read_pattern::{ReadFrom, PatternReader};
 pub use self::write_pattern::{WriteInto, PatternWriter};
 pub use self::external_size::ExternalSize;
+pub use self::fixed_size::FixedSize;
+pub use self::compute_size::{ComputeSize, SizeMatcher};
+pub use self::tracing::{TracingReader, TracingWriter, TraceEntry};
 
+use checksum::Checksum;
 use error::AsyncError;
-use pattern::combinators::UnexpectedValue;
+use pattern::combinators::{UnexpectedValue, UnknownTag, RecursionLimitExceeded};
 
 pub mod futures {
     //! I/O operation related futures.
-    pub use super::async_read::{ReadBytes, ReadNonEmpty, ReadExact};
-    pub use super::read_pattern::{ReadEos, ReadUntil, ReadBuf, ReadPartialBuf};
-    pub use super::read_pattern::{ReadString, ReadFixnum, ReadPattern};
+    pub use super::async_read::{ReadBytes, ReadNonEmpty, ReadExact, ReadToEnd, CopyTo, SkipBytes};
+    pub use super::read_pattern::{ReadEos, ReadUntil, ReadScan, ReadBuf, ReadPartialBuf};
+    pub use super::read_pattern::{ReadString, ReadFixnum, ReadPattern, ReadBufferedPattern};
     pub use super::read_pattern::{ReadLengthPrefixedBytes, ReadUtf8, ReadAll};
+    pub use super::read_pattern::{ReadPeek, ReadMany, ReadSepBy, ReadTerminatedBy, ReadChecksummed};
+    pub use super::read_pattern::{ReadSkip, ReadAt, ReadFixed, ReadWithState};
 
     pub use super::async_write::{Flush, WriteBytes, WriteAll};
     pub use super::write_pattern::{WritePattern, WriteBuf, WritePartialBuf};
-    pub use super::write_pattern::{WriteFixnum, WriteFlush};
+    pub use super::write_pattern::{WriteFixnum, WriteFlush, WriteChecksummed, WriteFill};
+    pub use super::write_pattern::{WriteAt, WriteWithState};
+
+    pub use super::tracing::MatchTraced;
 }
 pub mod streams {
     //! I/O operation related streams.
@@ -34,10 +45,34 @@ mod async_write;
 mod read_pattern;
 mod write_pattern;
 mod external_size;
+mod fixed_size;
+mod compute_size;
+mod tracing;
 
 /// I/O specific asynchronous error type.
 pub type AsyncIoError<T> = AsyncError<T, io::Error>;
 
+/// An error type which can be used by `PatternReader` and `PatternWriter`.
+///
+/// This trait is automatically implemented for all error types
+/// which can be converted from `std::io::Error`.
+pub trait FromIoError: error::Error + From<io::Error> {}
+impl<E: error::Error + From<io::Error>> FromIoError for E {}
+
+// A type erased checksum fed by `PatternReader` and `PatternWriter`.
+trait ChecksumHook: Any + Send {
+    fn update(&mut self, bytes: &[u8]);
+    fn into_any(self: Box<Self>) -> Box<dyn Any>;
+}
+impl<H: Checksum + Send + 'static> ChecksumHook for H {
+    fn update(&mut self, bytes: &[u8]) {
+        Checksum::update(self, bytes)
+    }
+    fn into_any(self: Box<Self>) -> Box<dyn Any> {
+        self
+    }
+}
+
 impl<T> From<UnexpectedValue<T>> for io::Error
 where
     T: fmt::Debug,
@@ -45,10 +80,66 @@ where
     fn from(f: UnexpectedValue<T>) -> Self {
         io::Error::new(
             io::ErrorKind::InvalidData,
-            format!("Unexpected value: {:?}", f.0),
+            format!("Unexpected value: {:?} (expected {})", f.0, f.expected()),
+        )
+    }
+}
+impl<T> From<UnknownTag<T>> for io::Error
+where
+    T: fmt::Debug,
+{
+    fn from(f: UnknownTag<T>) -> Self {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("Unknown tag: {:?}", f.0),
         )
     }
 }
+impl From<RecursionLimitExceeded> for io::Error {
+    fn from(_: RecursionLimitExceeded) -> Self {
+        io::Error::new(io::ErrorKind::InvalidData, "Recursion depth limit exceeded")
+    }
+}
+
+/// An error which indicates that the position of a stream could not be restored
+/// after the matching of an `At` pattern failed.
+///
+/// This is returned (wrapped in an `io::Error`) in place of the original error,
+/// and the error occurred in seeking back is available via `Error::source`.
+#[derive(Debug)]
+pub struct SeekBackError {
+    original: String,
+    error: io::Error,
+}
+impl SeekBackError {
+    /// Returns the description of the original error of the matching.
+    pub fn original(&self) -> &str {
+        &self.original
+    }
+}
+impl fmt::Display for SeekBackError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Cannot restore the position of the stream after an error ({}): {}",
+               self.original, self.error)
+    }
+}
+impl error::Error for SeekBackError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+// The inner future and the position to be restored, or the error occurred in seeking.
+type SeekResult<F, M, E> = Result<(F, u64), AsyncError<M, E>>;
+
+fn seek_back_error<E: FromIoError>(original: E, error: io::Error) -> E {
+    let kind = error.kind();
+    let error = SeekBackError {
+        original: original.to_string(),
+        error,
+    };
+    E::from(io::Error::new(kind, error))
+}
 
 
 /// Stateful I/O stream.
@@ -1,36 +1,236 @@
 use std;
-use std::io::{Read, Error, ErrorKind, Result};
-use futures::{Poll, Async, Future, Stream};
+use std::collections::VecDeque;
+use std::error;
+use std::marker::PhantomData;
+use std::io::{Read, Seek, SeekFrom, Error, ErrorKind, Result};
+use futures::{self, Poll, Async, Future, Stream};
 use byteorder::{ByteOrder, NativeEndian, BigEndian, LittleEndian};
 
 use io::AsyncRead;
 use io::futures::{ReadBytes, ReadExact, ReadNonEmpty};
-use pattern::{Buf, Window, TryAsLength, Branch};
+use pattern::{Pattern, Buf, Window, TryAsLength, Branch};
 use pattern::read;
-use pattern::combinators::{self, BE, LE, PartialBuf};
-use matcher::{AsyncMatch, Matcher};
+use pattern::combinators::{self, BE, LE, PartialBuf, Checksummed, WithState};
+use matcher::{AsyncMatch, Matcher, TrackPosition};
 use matcher::streams::MatchStream;
-use super::AsyncIoError;
+use error::{AsyncError, MatchError};
+use checksum::Checksum;
+use super::{AsyncIoError, FromIoError, ChecksumHook, FixedSize, Stateful};
+use super::{SeekResult, seek_back_error};
 
 /// A matcher to read patterns from the inner reader `R`.
 ///
 /// This is mainly used to define your own reading patterns.
 /// See the example of the [`ReadFrom`](./trait.ReadFrom.html) trait.
-pub struct PatternReader<R>(R);
+///
+/// The error type `E` of this matcher defaults to `std::io::Error`.
+/// Any error type which can be converted from `std::io::Error` can be used instead,
+/// so that patterns can fail with domain specific errors.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate futures;
+/// # extern crate handy_async;
+/// use std::fmt;
+/// use std::io;
+/// use futures::Future;
+/// use handy_async::io::PatternReader;
+/// use handy_async::matcher::AsyncMatch;
+/// use handy_async::pattern::Pattern;
+/// use handy_async::pattern::read::U8;
+///
+/// #[derive(Debug)]
+/// enum ProtocolError {
+///     Io(io::Error),
+///     UnknownVersion(u8),
+/// }
+/// impl From<io::Error> for ProtocolError {
+///     fn from(f: io::Error) -> Self {
+///         ProtocolError::Io(f)
+///     }
+/// }
+/// impl fmt::Display for ProtocolError {
+///     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+///         write!(f, "{:?}", self)
+///     }
+/// }
+/// impl std::error::Error for ProtocolError {}
+///
+/// # fn main() {
+/// let version = U8.and_then(|v| if v == 1 { Ok(v) } else { Err(ProtocolError::UnknownVersion(v)) });
+/// let reader = PatternReader::with_error_type(&[2][..]);
+/// let e = version.async_match(reader).wait().err().unwrap();
+/// assert!(matches!(e.error_ref(), ProtocolError::UnknownVersion(2)));
+/// # }
+/// ```
+pub struct PatternReader<R, E = Error> {
+    inner: R,
+    position: u64,
+    pending: VecDeque<u8>,
+    journal: Vec<u8>,
+    marks: Vec<usize>,
+    checksums: Vec<(Box<dyn ChecksumHook>, usize)>,
+    _error: PhantomData<E>,
+}
 impl<R: Read> PatternReader<R> {
     /// Makes new `PatternReader` instance.
     pub fn new(inner: R) -> Self {
-        PatternReader(inner)
+        Self::with_error_type(inner)
+    }
+}
+impl<R: Read, E: FromIoError> PatternReader<R, E> {
+    /// Makes new `PatternReader` instance which has the error type `E`.
+    pub fn with_error_type(inner: R) -> Self {
+        PatternReader {
+            inner,
+            position: 0,
+            pending: VecDeque::new(),
+            journal: Vec::new(),
+            marks: Vec::new(),
+            checksums: Vec::new(),
+            _error: PhantomData,
+        }
+    }
+}
+impl<R, E> PatternReader<R, E> {
+    /// Returns the reference to the inner reader.
+    pub fn inner_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Returns the mutable reference to the inner reader.
+    pub fn inner_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Unwraps this `PatternReader`, returning the inner reader.
+    ///
+    /// Note that bytes which have been looked ahead (e.g., by the `Peek` pattern)
+    /// but not consumed yet are discarded.
+    /// To keep them, continue reading from this `PatternReader` instead
+    /// (e.g., via `ReadFrom::read_from_buffered`).
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    /// Returns the total byte size read via this `PatternReader`.
+    ///
+    /// If this reader has been seeked, this is the offset from the start of the stream.
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+
+    /// Starts recording the bytes read from now on, so that they can be rewound later.
+    ///
+    /// Marks can be nested.
+    /// Each mark must be ended by either `rewind` or `release`.
+    fn mark(&mut self) {
+        self.marks.push(self.journal.len());
+    }
+
+    /// Pushes back the bytes read since the last mark, and ends the mark.
+    fn rewind(&mut self) {
+        let start = self.marks.pop().expect("No mark to rewind");
+        for &b in self.journal[start..].iter().rev() {
+            self.pending.push_front(b);
+        }
+        self.position -= (self.journal.len() - start) as u64;
+        self.journal.truncate(start);
+    }
+
+    /// Returns the bytes read since the last mark.
+    fn marked_bytes(&self) -> &[u8] {
+        let start = *self.marks.last().expect("No mark");
+        &self.journal[start..]
+    }
+
+    /// Ends the last mark, keeping the first `size` bytes read since then consumed
+    /// and pushing back the rest.
+    fn release_partially(&mut self, size: usize) {
+        let end = *self.marks.last().expect("No mark to release") + size;
+        for &b in self.journal[end..].iter().rev() {
+            self.pending.push_front(b);
+        }
+        self.position -= (self.journal.len() - end) as u64;
+        self.journal.truncate(end);
+        self.release();
+    }
+
+    /// Ends the last mark, keeping the bytes read since then consumed.
+    fn release(&mut self) {
+        self.marks.pop().expect("No mark to release");
+        if self.marks.is_empty() {
+            for &mut (ref mut checksum, ref mut start) in &mut self.checksums {
+                checksum.update(&self.journal[*start..]);
+                *start = 0;
+            }
+            self.journal.clear();
+        }
+    }
+
+    /// Starts feeding the bytes consumed from now on to a new checksum `H`.
+    ///
+    /// While marks exist, the bytes are fed when they are released
+    /// (i.e., the bytes which are rewound are not fed twice).
+    fn start_checksum<H: Checksum + Send + 'static>(&mut self) {
+        self.checksums.push((Box::new(H::default()), self.journal.len()));
+    }
+
+    /// Ends the last checksum started by `start_checksum`, and returns it.
+    fn finish_checksum<H: Checksum + 'static>(&mut self) -> H {
+        let (mut checksum, start) = self.checksums.pop().expect("No checksum to finish");
+        checksum.update(&self.journal[start..]);
+        *checksum.into_any().downcast().expect("Unexpected checksum type")
     }
 }
-impl<R: Read> Read for PatternReader<R> {
+impl<R: Read, E> Read for PatternReader<R, E> {
     fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
-        self.0.read(buf)
+        let size = if self.pending.is_empty() {
+            self.inner.read(buf)?
+        } else {
+            let size = std::cmp::min(buf.len(), self.pending.len());
+            for (b, p) in buf.iter_mut().zip(self.pending.drain(..size)) {
+                *b = p;
+            }
+            size
+        };
+        if !self.marks.is_empty() {
+            self.journal.extend_from_slice(&buf[..size]);
+        } else {
+            for &mut (ref mut checksum, _) in &mut self.checksums {
+                checksum.update(&buf[..size]);
+            }
+        }
+        self.position += size as u64;
+        Ok(size)
+    }
+}
+impl<R: Read + Seek, E> Seek for PatternReader<R, E> {
+    /// Seeks the inner reader.
+    ///
+    /// Bytes which have been looked ahead are discarded.
+    /// Seeking while looking ahead (e.g., in the `Peek` pattern) results in an error.
+    fn seek(&mut self, pos: SeekFrom) -> Result<u64> {
+        if !self.marks.is_empty() {
+            return Err(Error::new(ErrorKind::InvalidInput, "Cannot seek while looking ahead"));
+        }
+        let pos = match pos {
+            SeekFrom::Current(n) => SeekFrom::Current(n - self.pending.len() as i64),
+            _ => pos,
+        };
+        self.position = self.inner.seek(pos)?;
+        self.pending.clear();
+        Ok(self.position)
     }
 }
-impl<R> Matcher for PatternReader<R> {
-    type Error = Error;
+impl<R, E: error::Error> Matcher for PatternReader<R, E> {
+    type Error = E;
+    fn position(&self) -> Option<u64> {
+        Some(self.position)
+    }
 }
+impl<R, E: error::Error> TrackPosition for PatternReader<R, E> {}
 
 /// The `ReadFrom` trait allows for reading a value of the pattern from a source asynchronously.
 ///
@@ -90,6 +290,11 @@ impl<R> Matcher for PatternReader<R> {
 pub trait ReadFrom<R: Read>: AsyncMatch<PatternReader<R>> {
     /// Creates a future instance to read a value of the pattern from `reader`.
     ///
+    /// Note that bytes which have been looked ahead but not consumed
+    /// (e.g., by the `Peek` or `Scan` patterns) are discarded
+    /// when the reader is returned.
+    /// Use `ReadFrom::read_from_buffered` to keep them.
+    ///
     /// # Examples
     ///
     /// ```
@@ -108,7 +313,33 @@ pub trait ReadFrom<R: Read>: AsyncMatch<PatternReader<R>> {
     /// # }
     /// ```
     fn read_from(self, reader: R) -> ReadPattern<Self, R> {
-        ReadPattern(self.async_match(PatternReader(reader)))
+        ReadPattern(self.async_match(PatternReader::new(reader)))
+    }
+
+    /// Creates a future instance to read a value of the pattern from `reader`,
+    /// which returns the reader wrapped in a `PatternReader`.
+    ///
+    /// Unlike `ReadFrom::read_from`, bytes which have been looked ahead but not consumed
+    /// are kept in the returned `PatternReader`, so they can be read by the following patterns.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate futures;
+    /// # extern crate handy_async;
+    /// use handy_async::io::ReadFrom;
+    /// use handy_async::pattern::read::{All, Scan};
+    /// use futures::Future;
+    ///
+    /// # fn main() {
+    /// let (rest, first) = Scan::new(b';').read_from_buffered(&b"foo;bar"[..]).wait().unwrap();
+    /// assert_eq!(first, b"foo;");
+    /// let (_, rest) = All.read_from(rest).wait().unwrap();
+    /// assert_eq!(rest, b"bar");
+    /// # }
+    /// ```
+    fn read_from_buffered(self, reader: R) -> ReadBufferedPattern<Self, R> {
+        ReadBufferedPattern(self.async_match(PatternReader::new(reader)))
     }
 
     /// Synchronous version of the `ReadFrom::read_from` method.
@@ -139,7 +370,7 @@ pub trait ReadFrom<R: Read>: AsyncMatch<PatternReader<R>> {
     where
         Self: Clone,
     {
-        ReadStream(AsyncMatch::into_stream(self, PatternReader(reader)))
+        ReadStream(AsyncMatch::into_stream(self, PatternReader::new(reader)))
     }
 }
 impl<R: Read, T> ReadFrom<R> for T
@@ -161,7 +392,7 @@ where
     type Item = P::Value;
     type Error = AsyncIoError<R>;
     fn poll(&mut self) -> Poll<Option<Self::Item>, Self::Error> {
-        self.0.poll().map_err(|e| e.map_state(|r| r.0))
+        self.0.poll().map_err(into_reader_error)
     }
 }
 
@@ -178,14 +409,52 @@ where
     type Item = (R, P::Value);
     type Error = AsyncIoError<R>;
     fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
-        Ok(self.0.poll().map_err(|e| e.map_state(|m| m.0))?.map(
-            |(m, v)| {
-                (m.0, v)
-            },
+        Ok(self.0.poll().map_err(into_reader_error)?.map(
+            |(m, v)| (m.inner, v),
         ))
     }
 }
 
+/// Future to match between a pattern `P` and bytes read from `R`,
+/// which keeps the bytes looked ahead in the resulting reader.
+///
+/// This is created by calling `ReadFrom::read_from_buffered` method.
+pub struct ReadBufferedPattern<P, R>(P::Future)
+where
+    P: AsyncMatch<PatternReader<R>>;
+impl<P, R> Future for ReadBufferedPattern<P, R>
+where
+    P: AsyncMatch<PatternReader<R>>,
+{
+    type Item = (PatternReader<R>, P::Value);
+    type Error = AsyncIoError<PatternReader<R>>;
+    fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
+        self.0.poll().map_err(with_offset)
+    }
+}
+
+fn into_reader_error<R>(e: AsyncIoError<PatternReader<R>>) -> AsyncIoError<R> {
+    with_offset(e).map_state(|r| r.inner)
+}
+
+fn with_offset<R>(e: AsyncIoError<PatternReader<R>>) -> AsyncIoError<PatternReader<R>> {
+    match e.offset() {
+        None => {
+            let offset = e.state_ref().position;
+            e.with_offset(offset)
+        }
+        Some(_) => e,
+    }
+}
+
+fn into_matcher_error<R, E, B>(e: AsyncIoError<(PatternReader<R, E>, B)>)
+                              -> AsyncError<PatternReader<R, E>, E>
+where
+    E: From<Error>,
+{
+    e.map_state(|(r, _)| r).map_error(E::from)
+}
+
 /// A future which will read bytes from `R` to fill the buffer `B` completely.
 ///
 /// This future is generally created by invoking
@@ -200,12 +469,12 @@ where
 /// Buf([0; 32]).read_from(std::io::empty());
 /// Window::new([0; 32]).skip(4).read_from(std::io::empty());
 /// ```
-pub struct ReadBuf<R, B>(ReadExact<PatternReader<R>, Buf<B>>);
-impl<R: Read, B: AsMut<[u8]>> Future for ReadBuf<R, B> {
-    type Item = (PatternReader<R>, B);
-    type Error = AsyncIoError<PatternReader<R>>;
+pub struct ReadBuf<R, B, E = Error>(ReadExact<PatternReader<R, E>, Buf<B>>);
+impl<R: Read, E: FromIoError, B: AsMut<[u8]>> Future for ReadBuf<R, B, E> {
+    type Item = (PatternReader<R, E>, B);
+    type Error = AsyncError<PatternReader<R, E>, E>;
     fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
-        Ok(self.0.poll().map_err(|e| e.map_state(|(r, _)| r))?.map(
+        Ok(self.0.poll().map_err(into_matcher_error)?.map(
             |(r,
               v)| {
                 (r, v.0)
@@ -213,21 +482,21 @@ impl<R: Read, B: AsMut<[u8]>> Future for ReadBuf<R, B> {
         ))
     }
 }
-impl<R: Read, B: AsMut<[u8]>> AsyncMatch<PatternReader<R>> for Buf<B> {
-    type Future = ReadBuf<R, B>;
-    fn async_match(self, matcher: PatternReader<R>) -> Self::Future {
+impl<R: Read, E: FromIoError, B: AsMut<[u8]>> AsyncMatch<PatternReader<R, E>> for Buf<B> {
+    type Future = ReadBuf<R, B, E>;
+    fn async_match(self, matcher: PatternReader<R, E>) -> Self::Future {
         ReadBuf(matcher.async_read_exact(self))
     }
 }
-impl<R: Read> AsyncMatch<PatternReader<R>> for Vec<u8> {
-    type Future = ReadBuf<R, Self>;
-    fn async_match(self, matcher: PatternReader<R>) -> Self::Future {
+impl<R: Read, E: FromIoError> AsyncMatch<PatternReader<R, E>> for Vec<u8> {
+    type Future = ReadBuf<R, Self, E>;
+    fn async_match(self, matcher: PatternReader<R, E>) -> Self::Future {
         Buf(self).async_match(matcher)
     }
 }
-impl<R: Read, B: AsMut<[u8]>> AsyncMatch<PatternReader<R>> for Window<B> {
-    type Future = ReadBuf<R, Self>;
-    fn async_match(self, matcher: PatternReader<R>) -> Self::Future {
+impl<R: Read, E: FromIoError, B: AsMut<[u8]>> AsyncMatch<PatternReader<R, E>> for Window<B> {
+    type Future = ReadBuf<R, Self, E>;
+    fn async_match(self, matcher: PatternReader<R, E>) -> Self::Future {
         Buf(self).async_match(matcher)
     }
 }
@@ -253,20 +522,20 @@ impl<R: Read, B: AsMut<[u8]>> AsyncMatch<PatternReader<R>> for Window<B> {
 /// assert_eq!(read_size, 4);
 /// # }
 /// ```
-pub struct ReadPartialBuf<R, B>(ReadNonEmpty<PatternReader<R>, B>);
-impl<R: Read, B: AsMut<[u8]>> Future for ReadPartialBuf<R, B> {
-    type Item = (PatternReader<R>, (B, usize));
-    type Error = AsyncIoError<PatternReader<R>>;
+pub struct ReadPartialBuf<R, B, E = Error>(ReadNonEmpty<PatternReader<R, E>, B>);
+impl<R: Read, E: FromIoError, B: AsMut<[u8]>> Future for ReadPartialBuf<R, B, E> {
+    type Item = (PatternReader<R, E>, (B, usize));
+    type Error = AsyncError<PatternReader<R, E>, E>;
     fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
         self.0
             .poll()
             .map(|x| x.map(|(r, b, s)| (r, (b, s))))
-            .map_err(|e| e.map_state(|(r, _)| r))
+            .map_err(into_matcher_error)
     }
 }
-impl<R: Read, B: AsMut<[u8]>> AsyncMatch<PatternReader<R>> for PartialBuf<B> {
-    type Future = ReadPartialBuf<R, B>;
-    fn async_match(self, matcher: PatternReader<R>) -> Self::Future {
+impl<R: Read, E: FromIoError, B: AsMut<[u8]>> AsyncMatch<PatternReader<R, E>> for PartialBuf<B> {
+    type Future = ReadPartialBuf<R, B, E>;
+    fn async_match(self, matcher: PatternReader<R, E>) -> Self::Future {
         ReadPartialBuf(matcher.async_read_non_empty(self.0))
     }
 }
@@ -284,18 +553,18 @@ impl<R: Read, B: AsMut<[u8]>> AsyncMatch<PatternReader<R>> for PartialBuf<B> {
 /// let str_buf = String::from_utf8(vec![0; 32]).unwrap();
 /// str_buf.read_from(std::io::empty()); // This returns a `ReadString` instance
 /// ```
-pub struct ReadString<R>(ReadExact<PatternReader<R>, Vec<u8>>);
-impl<R: Read> Future for ReadString<R> {
-    type Item = (PatternReader<R>, String);
-    type Error = AsyncIoError<PatternReader<R>>;
+pub struct ReadString<R, E = Error>(ReadExact<PatternReader<R, E>, Vec<u8>>);
+impl<R: Read, E: FromIoError> Future for ReadString<R, E> {
+    type Item = (PatternReader<R, E>, String);
+    type Error = AsyncError<PatternReader<R, E>, E>;
     fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
-        if let Async::Ready((r, b)) = self.0.poll().map_err(|e| e.map_state(|(r, _)| r))? {
+        if let Async::Ready((r, b)) = self.0.poll().map_err(into_matcher_error)? {
             match String::from_utf8(b) {
                 Ok(s) => Ok(Async::Ready((r, s))),
                 Err(e) => {
-                    Err(AsyncIoError::new(
+                    Err(AsyncError::new(
                         r,
-                        Error::new(ErrorKind::InvalidData, Box::new(e)),
+                        E::from(Error::new(ErrorKind::InvalidData, Box::new(e))),
                     ))
                 }
             }
@@ -304,13 +573,17 @@ impl<R: Read> Future for ReadString<R> {
         }
     }
 }
-impl<R: Read> AsyncMatch<PatternReader<R>> for String {
-    type Future = ReadString<R>;
-    fn async_match(self, matcher: PatternReader<R>) -> Self::Future {
+impl<R: Read, E: FromIoError> AsyncMatch<PatternReader<R, E>> for String {
+    type Future = ReadString<R, E>;
+    fn async_match(self, matcher: PatternReader<R, E>) -> Self::Future {
         ReadString(matcher.async_read_exact(self.into_bytes()))
     }
 }
 
+type LengthPrefixedFuture<R, P, E> =
+    <combinators::AndThen<P, BranchFun<<P as Pattern>::Value, E>> as
+     AsyncMatch<PatternReader<R, E>>>::Future;
+
 /// A future which will read a length-prefixed bytes.
 ///
 /// This is created by calling `ReadFrom::read_from` method for `LengthPrefixedBytes` pattern.
@@ -326,36 +599,35 @@ impl<R: Read> AsyncMatch<PatternReader<R>> for String {
 /// let bytes = LengthPrefixedBytes(U8).sync_read_from(&input[..]).unwrap();
 /// assert_eq!(bytes, b"hel");
 /// ```
-pub struct ReadLengthPrefixedBytes<R: Read, P>(
-    <combinators::AndThen<P, BranchFun<P::Value>> as
-     AsyncMatch<PatternReader<R>>>::Future) where P: AsyncMatch<PatternReader<R>>;
-impl<R: Read, P> Future for ReadLengthPrefixedBytes<R, P>
+pub struct ReadLengthPrefixedBytes<R: Read, P, E: FromIoError = Error>(
+    LengthPrefixedFuture<R, P, E>) where P: AsyncMatch<PatternReader<R, E>>;
+impl<R: Read, E: FromIoError, P> Future for ReadLengthPrefixedBytes<R, P, E>
 where
-    P: AsyncMatch<PatternReader<R>>,
+    P: AsyncMatch<PatternReader<R, E>>,
 {
-    type Item = (PatternReader<R>, Vec<u8>);
-    type Error = AsyncIoError<PatternReader<R>>;
+    type Item = (PatternReader<R, E>, Vec<u8>);
+    type Error = AsyncError<PatternReader<R, E>, E>;
     fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
         self.0.poll()
     }
 }
-impl<R: Read, P> AsyncMatch<PatternReader<R>> for read::LengthPrefixedBytes<P>
-    where P: AsyncMatch<PatternReader<R>>,
+impl<R: Read, E: FromIoError, P> AsyncMatch<PatternReader<R, E>> for read::LengthPrefixedBytes<P>
+    where P: AsyncMatch<PatternReader<R, E>>,
           P::Value: TryAsLength
 {
-    type Future = ReadLengthPrefixedBytes<R, P>;
-    fn async_match(self, matcher: PatternReader<R>) -> Self::Future {
+    type Future = ReadLengthPrefixedBytes<R, P, E>;
+    fn async_match(self, matcher: PatternReader<R, E>) -> Self::Future {
         let conv: fn(P::Value) -> _ = |len| {
             if let Some(len) = len.try_as_length() {
                 Branch::A(vec![0; len])
             } else {
-                Branch::B(Err(Error::new(ErrorKind::InvalidData, "Too large length")))
+                Branch::B(Err(E::from(Error::new(ErrorKind::InvalidData, "Too large length"))))
             }
         };
         ReadLengthPrefixedBytes(self.0.and_then(conv).async_match(matcher))
     }
 }
-type BranchFun<V> = fn(V) -> Branch<Vec<u8>, Result<Vec<u8>>>;
+type BranchFun<V, E> = fn(V) -> Branch<Vec<u8>, std::result::Result<Vec<u8>, E>>;
 
 /// A future which will read all bytes remaining in a stream.
 ///
@@ -369,12 +641,12 @@ type BranchFun<V> = fn(V) -> Branch<Vec<u8>, Result<Vec<u8>>>;
 /// let (_, _, bytes) = (U8, U8, All).sync_read_from(&b"hello"[..]).unwrap();
 /// assert_eq!(bytes, b"llo");
 /// ```
-pub struct ReadAll<R: Read>(ReadBytes<PatternReader<R>, Window<Vec<u8>>>);
-impl<R: Read> Future for ReadAll<R> {
-    type Item = (PatternReader<R>, Vec<u8>);
-    type Error = AsyncIoError<PatternReader<R>>;
+pub struct ReadAll<R: Read, E = Error>(ReadBytes<PatternReader<R, E>, Window<Vec<u8>>>);
+impl<R: Read, E: FromIoError> Future for ReadAll<R, E> {
+    type Item = (PatternReader<R, E>, Vec<u8>);
+    type Error = AsyncError<PatternReader<R, E>, E>;
     fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
-        while let Async::Ready((r, b, size)) = self.0.poll().map_err(|e| e.map_state(|(r, _)| r))? {
+        while let Async::Ready((r, b, size)) = self.0.poll().map_err(into_matcher_error)? {
             if size == 0 {
                 let total_read_size = b.start();
                 let mut b = b.into_inner();
@@ -393,9 +665,9 @@ impl<R: Read> Future for ReadAll<R> {
         Ok(Async::NotReady)
     }
 }
-impl<R: Read> AsyncMatch<PatternReader<R>> for read::All {
-    type Future = ReadAll<R>;
-    fn async_match(self, matcher: PatternReader<R>) -> Self::Future {
+impl<R: Read, E: FromIoError> AsyncMatch<PatternReader<R, E>> for read::All {
+    type Future = ReadAll<R, E>;
+    fn async_match(self, matcher: PatternReader<R, E>) -> Self::Future {
         let b = Window::new(vec![0; 256]);
         ReadAll(matcher.async_read(b))
     }
@@ -416,23 +688,23 @@ impl<R: Read> AsyncMatch<PatternReader<R>> for read::All {
 /// let string = Utf8(LengthPrefixedBytes(U8)).sync_read_from(&input[..]).unwrap();
 /// assert_eq!(string, "hel");
 /// ```
-pub struct ReadUtf8<R: Read, P>(P::Future)
+pub struct ReadUtf8<R: Read, P, E: FromIoError = Error>(P::Future)
 where
-    P: AsyncMatch<PatternReader<R>>;
-impl<R: Read, P> Future for ReadUtf8<R, P>
+    P: AsyncMatch<PatternReader<R, E>>;
+impl<R: Read, E: FromIoError, P> Future for ReadUtf8<R, P, E>
 where
-    P: AsyncMatch<PatternReader<R>>,
+    P: AsyncMatch<PatternReader<R, E>>,
     Vec<u8>: From<P::Value>,
 {
-    type Item = (PatternReader<R>, String);
-    type Error = AsyncIoError<PatternReader<R>>;
+    type Item = (PatternReader<R, E>, String);
+    type Error = AsyncError<PatternReader<R, E>, E>;
     fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
         if let Async::Ready((m, b)) = self.0.poll()? {
             match String::from_utf8(Vec::from(b)) {
                 Err(e) => {
-                    Err(AsyncIoError::new(
+                    Err(AsyncError::new(
                         m,
-                        Error::new(ErrorKind::InvalidData, Box::new(e)),
+                        E::from(Error::new(ErrorKind::InvalidData, Box::new(e))),
                     ))
                 }
                 Ok(s) => Ok(Async::Ready((m, s))),
@@ -442,31 +714,31 @@ where
         }
     }
 }
-impl<R: Read, P> AsyncMatch<PatternReader<R>> for read::Utf8<P>
+impl<R: Read, E: FromIoError, P> AsyncMatch<PatternReader<R, E>> for read::Utf8<P>
 where
-    P: AsyncMatch<PatternReader<R>>,
+    P: AsyncMatch<PatternReader<R, E>>,
     Vec<u8>: From<P::Value>,
 {
-    type Future = ReadUtf8<R, P>;
-    fn async_match(self, matcher: PatternReader<R>) -> Self::Future {
+    type Future = ReadUtf8<R, P, E>;
+    fn async_match(self, matcher: PatternReader<R, E>) -> Self::Future {
         ReadUtf8(self.0.async_match(matcher))
     }
 }
 
 /// A future which will read a fixnum associated with `P` from `R`.
-pub struct ReadFixnum<R, P, T>
+pub struct ReadFixnum<R, P, T, E: FromIoError = Error>
 where
-    P: AsyncMatch<PatternReader<R>>,
+    P: AsyncMatch<PatternReader<R, E>>,
 {
     future: P::Future,
     convert: fn(P::Value) -> T,
 }
-impl<R: Read, P, T> Future for ReadFixnum<R, P, T>
+impl<R: Read, E: FromIoError, P, T> Future for ReadFixnum<R, P, T, E>
 where
-    P: AsyncMatch<PatternReader<R>>,
+    P: AsyncMatch<PatternReader<R, E>>,
 {
-    type Item = (PatternReader<R>, T);
-    type Error = AsyncIoError<PatternReader<R>>;
+    type Item = (PatternReader<R, E>, T);
+    type Error = AsyncError<PatternReader<R, E>, E>;
     fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
         Ok(self.future.poll()?.map(|(r, v)| (r, (self.convert)(v))))
     }
@@ -474,9 +746,9 @@ where
 
 macro_rules! impl_read_fixnum_pattern {
     ($pat:ty, $val:ident, $size:expr, $conv:expr) => {
-        impl<R: Read> AsyncMatch<PatternReader<R>> for $pat {
-            type Future = ReadFixnum<R, Buf<[u8; $size]>, Self::Value>;
-            fn async_match(self, matcher: PatternReader<R>) -> Self::Future {
+        impl<R: Read, E: FromIoError> AsyncMatch<PatternReader<R, E>> for $pat {
+            type Future = ReadFixnum<R, Buf<[u8; $size]>, Self::Value, E>;
+            fn async_match(self, matcher: PatternReader<R, E>) -> Self::Future {
                 fn conv(b: [u8; $size]) -> $val {
                     $conv(&b[..]) as $val
                 }
@@ -484,6 +756,12 @@ macro_rules! impl_read_fixnum_pattern {
                 ReadFixnum{future: future, convert: conv}
             }
         }
+        impl FixedSize for $pat {
+            const SIZE: usize = $size;
+            fn decode(self, b: &[u8]) -> $val {
+                $conv(&b[..$size]) as $val
+            }
+        }
     }
 }
 
@@ -571,10 +849,10 @@ impl_read_fixnum_pattern!(LE<read::F64>, f64, 8, |b: &[u8]| LittleEndian::read_f
 /// assert_eq!(is_eos, Err(3));
 /// # }
 /// ```
-pub struct ReadEos<R>(ReadExact<PatternReader<R>, [u8; 1]>);
-impl<R: Read> Future for ReadEos<R> {
-    type Item = (PatternReader<R>, std::result::Result<(), u8>);
-    type Error = AsyncIoError<PatternReader<R>>;
+pub struct ReadEos<R, E = Error>(ReadExact<PatternReader<R, E>, [u8; 1]>);
+impl<R: Read, E: FromIoError> Future for ReadEos<R, E> {
+    type Item = (PatternReader<R, E>, std::result::Result<(), u8>);
+    type Error = AsyncError<PatternReader<R, E>, E>;
     fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
         match self.0.poll() {
             Err(e) => {
@@ -582,7 +860,7 @@ impl<R: Read> Future for ReadEos<R> {
                     let ((r, _), _) = e.unwrap();
                     Ok(Async::Ready((r, Ok(()))))
                 } else {
-                    Err(e.map_state(|(r, _)| r))
+                    Err(into_matcher_error(e))
                 }
             }
             Ok(Async::Ready((r, b))) => Ok(Async::Ready((r, Err(b[0])))),
@@ -590,13 +868,121 @@ impl<R: Read> Future for ReadEos<R> {
         }
     }
 }
-impl<R: Read> AsyncMatch<PatternReader<R>> for read::Eos {
-    type Future = ReadEos<R>;
-    fn async_match(self, matcher: PatternReader<R>) -> Self::Future {
+impl<R: Read, E: FromIoError> AsyncMatch<PatternReader<R, E>> for read::Eos {
+    type Future = ReadEos<R, E>;
+    fn async_match(self, matcher: PatternReader<R, E>) -> Self::Future {
         ReadEos(matcher.async_read_exact([0; 1]))
     }
 }
 
+// A buffer for `ReadFixed`, which is allocated in the stack if the size is small enough.
+enum FixedBuf {
+    Stack([u8; 64], usize),
+    Heap(Vec<u8>),
+}
+impl FixedBuf {
+    fn new(size: usize) -> Self {
+        if size <= 64 {
+            FixedBuf::Stack([0; 64], size)
+        } else {
+            FixedBuf::Heap(vec![0; size])
+        }
+    }
+}
+impl AsRef<[u8]> for FixedBuf {
+    fn as_ref(&self) -> &[u8] {
+        match *self {
+            FixedBuf::Stack(ref b, size) => &b[..size],
+            FixedBuf::Heap(ref b) => b,
+        }
+    }
+}
+impl AsMut<[u8]> for FixedBuf {
+    fn as_mut(&mut self) -> &mut [u8] {
+        match *self {
+            FixedBuf::Stack(ref mut b, size) => &mut b[..size],
+            FixedBuf::Heap(ref mut b) => b,
+        }
+    }
+}
+
+/// A future which will read the bytes of a fixed size pattern at once and decode them.
+///
+/// This future is generally created by invoking
+/// `ReadFrom::read_from` method for `Fixed` pattern.
+pub struct ReadFixed<R, P, E = Error> {
+    future: ReadExact<PatternReader<R, E>, FixedBuf>,
+    pattern: Option<P>,
+}
+impl<R: Read, E: FromIoError, P: FixedSize> Future for ReadFixed<R, P, E> {
+    type Item = (PatternReader<R, E>, P::Value);
+    type Error = AsyncError<PatternReader<R, E>, E>;
+    fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
+        if let Async::Ready((r, b)) = self.future.poll().map_err(into_matcher_error)? {
+            let pattern = self.pattern.take().expect("Cannot poll ReadFixed twice");
+            Ok(Async::Ready((r, pattern.decode(b.as_ref()))))
+        } else {
+            Ok(Async::NotReady)
+        }
+    }
+}
+impl<R: Read, E: FromIoError, P: FixedSize> AsyncMatch<PatternReader<R, E>> for read::Fixed<P> {
+    type Future = ReadFixed<R, P, E>;
+    fn async_match(self, matcher: PatternReader<R, E>) -> Self::Future {
+        ReadFixed {
+            future: matcher.async_read_exact(FixedBuf::new(P::SIZE)),
+            pattern: Some(self.0),
+        }
+    }
+}
+
+/// A future which will discard bytes.
+///
+/// This future is generally created by invoking
+/// `ReadFrom::read_from` method for `Skip` or `Align` pattern.
+pub struct ReadSkip<R, E = Error> {
+    future: ReadExact<PatternReader<R, E>, Window<[u8; 256]>>,
+    remaining: usize,
+}
+impl<R: Read, E: FromIoError> ReadSkip<R, E> {
+    fn new(reader: PatternReader<R, E>, size: usize) -> Self {
+        let chunk = std::cmp::min(size, 256);
+        ReadSkip {
+            future: reader.async_read_exact(Window::new([0; 256]).set_end(chunk)),
+            remaining: size - chunk,
+        }
+    }
+}
+impl<R: Read, E: FromIoError> Future for ReadSkip<R, E> {
+    type Item = (PatternReader<R, E>, ());
+    type Error = AsyncError<PatternReader<R, E>, E>;
+    fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
+        while let Async::Ready((r, b)) = self.future.poll().map_err(into_matcher_error)? {
+            if self.remaining == 0 {
+                return Ok(Async::Ready((r, ())));
+            }
+            let chunk = std::cmp::min(self.remaining, 256);
+            self.remaining -= chunk;
+            self.future = r.async_read_exact(b.set_end(chunk));
+        }
+        Ok(Async::NotReady)
+    }
+}
+impl<R: Read, E: FromIoError> AsyncMatch<PatternReader<R, E>> for read::Skip {
+    type Future = ReadSkip<R, E>;
+    fn async_match(self, matcher: PatternReader<R, E>) -> Self::Future {
+        ReadSkip::new(matcher, self.0)
+    }
+}
+impl<R: Read, E: FromIoError> AsyncMatch<PatternReader<R, E>> for read::Align {
+    type Future = ReadSkip<R, E>;
+    fn async_match(self, matcher: PatternReader<R, E>) -> Self::Future {
+        let align = std::cmp::max(self.0, 1);
+        let padding = (align - matcher.position() % align) % align;
+        ReadSkip::new(matcher, padding as usize)
+    }
+}
+
 /// A future which will read a line string.
 ///
 /// A line is ended with a newline character `\n`.
@@ -628,10 +1014,10 @@ impl<R: Read> AsyncMatch<PatternReader<R>> for read::Eos {
 /// assert_eq!(e.error_ref().kind(), ErrorKind::UnexpectedEof);
 /// # }
 /// ```
-pub struct ReadLine<R>(Option<(PatternReader<R>, Vec<u8>)>);
-impl<R: Read> Future for ReadLine<R> {
-    type Item = (PatternReader<R>, String);
-    type Error = AsyncIoError<PatternReader<R>>;
+pub struct ReadLine<R, E = Error>(Option<(PatternReader<R, E>, Vec<u8>)>);
+impl<R: Read, E: FromIoError> Future for ReadLine<R, E> {
+    type Item = (PatternReader<R, E>, String);
+    type Error = AsyncError<PatternReader<R, E>, E>;
     fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
         let (mut reader, mut buf) = self.0.take().expect("Cannot poll ReadLine twice");
 
@@ -642,12 +1028,12 @@ impl<R: Read> Future for ReadLine<R> {
                     self.0 = Some((reader, buf));
                     Ok(Async::NotReady)
                 } else {
-                    Err(AsyncIoError::new(reader, e))
+                    Err(AsyncError::new(reader, E::from(e)))
                 }
             }
             Ok(0) if buf.is_empty() => {
                 let e = Error::new(ErrorKind::UnexpectedEof, "Cannot read a line");
-                Err(AsyncIoError::new(reader, e))
+                Err(AsyncError::new(reader, E::from(e)))
             }
             Ok(read_size) => {
                 let newline = if read_size == 0 {
@@ -661,7 +1047,7 @@ impl<R: Read> Future for ReadLine<R> {
                     match String::from_utf8(buf) {
                         Err(e) => {
                             let e = Error::new(ErrorKind::InvalidInput, Box::new(e));
-                            Err(AsyncIoError::new(reader, e))
+                            Err(AsyncError::new(reader, E::from(e)))
                         }
                         Ok(line) => Ok(Async::Ready((reader, line))),
                     }
@@ -673,9 +1059,9 @@ impl<R: Read> Future for ReadLine<R> {
         }
     }
 }
-impl<R: Read> AsyncMatch<PatternReader<R>> for read::Line {
-    type Future = ReadLine<R>;
-    fn async_match(self, matcher: PatternReader<R>) -> Self::Future {
+impl<R: Read, E: FromIoError> AsyncMatch<PatternReader<R, E>> for read::Line {
+    type Future = ReadLine<R, E>;
+    fn async_match(self, matcher: PatternReader<R, E>) -> Self::Future {
         ReadLine(Some((matcher, Vec::new())))
     }
 }
@@ -684,30 +1070,30 @@ impl<R: Read> AsyncMatch<PatternReader<R>> for read::Line {
 ///
 /// This future is generally created by invoking
 /// `ReadFrom::read_from` method for `Until` pattern.
-pub struct ReadUntil<R, F, T>
+pub struct ReadUntil<R, F, T, E = Error>
 where
     R: Read,
     F: Fn(&[u8], bool) -> Result<Option<T>>,
 {
-    read: ReadBytes<PatternReader<R>, Window<Vec<u8>>>,
+    read: ReadBytes<PatternReader<R, E>, Window<Vec<u8>>>,
     pred: F,
     max_buffer_size: usize,
 }
-impl<R: Read, F, T> Future for ReadUntil<R, F, T>
+impl<R: Read, E: FromIoError, F, T> Future for ReadUntil<R, F, T, E>
 where
     F: Fn(&[u8], bool) -> Result<Option<T>>,
 {
-    type Item = (PatternReader<R>, (Vec<u8>, T));
-    type Error = AsyncIoError<PatternReader<R>>;
+    type Item = (PatternReader<R, E>, (Vec<u8>, T));
+    type Error = AsyncError<PatternReader<R, E>, E>;
     fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
         if let Async::Ready((r, mut b, read_size)) =
-            self.read.poll().map_err(|e| e.map_state(|(r, _)| r))?
+            self.read.poll().map_err(into_matcher_error)?
         {
             let is_eos = read_size == 0;
             b = b.skip(read_size);
             let total_read_size = b.start();
             match (self.pred)(&b.inner_ref()[0..total_read_size], is_eos) {
-                Err(e) => Err(AsyncIoError::new(r, e)),
+                Err(e) => Err(AsyncError::new(r, E::from(e))),
                 Ok(Some(v)) => {
                     let mut b = b.into_inner();
                     b.truncate(total_read_size);
@@ -715,7 +1101,7 @@ where
                 }
                 Ok(None) if is_eos => {
                     let e = Error::new(ErrorKind::UnexpectedEof, "Unexpected Eof");
-                    Err(AsyncIoError::new(r, e))
+                    Err(AsyncError::new(r, E::from(e)))
                 }
                 Ok(None) => {
                     if b.as_ref().is_empty() {
@@ -725,7 +1111,8 @@ where
                         if new_len == inner.len() {
                             let message = format!("Buffer size limit ({} bytes) reached",
                                                   self.max_buffer_size);
-                            return Err(AsyncIoError::new(r, Error::new(ErrorKind::Other, message)));
+                            let e = Error::new(ErrorKind::Other, message);
+                            return Err(AsyncError::new(r, E::from(e)));
                         }
                         inner.resize(total_read_size * 2, 0);
                         b = Window::new(inner).skip(total_read_size);
@@ -739,13 +1126,13 @@ where
         }
     }
 }
-impl<R: Read, F, T> AsyncMatch<PatternReader<R>> for read::Until<F, T>
+impl<R: Read, E: FromIoError, F, T> AsyncMatch<PatternReader<R, E>> for read::Until<F, T>
 where
     F: Fn(&[u8], bool)
        -> Result<Option<T>>,
 {
-    type Future = ReadUntil<R, F, T>;
-    fn async_match(self, matcher: PatternReader<R>) -> Self::Future {
+    type Future = ReadUntil<R, F, T, E>;
+    fn async_match(self, matcher: PatternReader<R, E>) -> Self::Future {
         let (pred, min_buffer_size, max_buffer_size) = self.unwrap();
         let buf = vec![0; min_buffer_size];
         ReadUntil {
@@ -756,6 +1143,602 @@ where
     }
 }
 
+/// A future which continues reading until the scanner `S` finds the end.
+///
+/// This future is generally created by invoking
+/// `ReadFrom::read_from` method for `Scan` pattern.
+pub struct ReadScan<R: Read, S, E = Error> {
+    read: ReadBytes<PatternReader<R, E>, Vec<u8>>,
+    scanner: S,
+    offset: usize,
+    max_size: usize,
+}
+impl<R: Read, S: read::Scanner, E: FromIoError> Future for ReadScan<R, S, E> {
+    type Item = (PatternReader<R, E>, Vec<u8>);
+    type Error = AsyncError<PatternReader<R, E>, E>;
+    fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
+        loop {
+            let (mut r, b, read_size) = match self.read.poll() {
+                Err(e) => {
+                    return Err(into_matcher_error(e).map_state(|mut r| {
+                        r.rewind();
+                        r
+                    }))
+                }
+                Ok(Async::NotReady) => return Ok(Async::NotReady),
+                Ok(Async::Ready(x)) => x,
+            };
+            if read_size == 0 {
+                r.rewind();
+                let e = Error::new(ErrorKind::UnexpectedEof, "Unexpected Eof");
+                return Err(AsyncError::new(r, E::from(e)));
+            }
+            let found = self.scanner.scan(&b[..read_size], self.offset);
+            if found.map_or(self.offset + read_size >= self.max_size, |end| end > self.max_size) {
+                r.rewind();
+                let message = format!("Scan size limit ({} bytes) reached", self.max_size);
+                let e = Error::new(ErrorKind::InvalidData, message);
+                return Err(AsyncError::new(r, E::from(e)));
+            }
+            if let Some(end) = found {
+                // Consumes the bytes up to the end, and pushes back the rest.
+                let bytes = r.marked_bytes()[..end].to_vec();
+                r.release_partially(end);
+                return Ok(Async::Ready((r, bytes)));
+            }
+            self.offset += read_size;
+            self.read = r.async_read(b);
+        }
+    }
+}
+impl<R: Read, S: read::Scanner, E: FromIoError> AsyncMatch<PatternReader<R, E>> for read::Scan<S> {
+    type Future = ReadScan<R, S, E>;
+    fn async_match(self, mut matcher: PatternReader<R, E>) -> Self::Future {
+        let (scanner, chunk_size, max_size) = self.unwrap();
+        matcher.mark();
+        ReadScan {
+            read: matcher.async_read(vec![0; chunk_size]),
+            scanner,
+            offset: 0,
+            max_size,
+        }
+    }
+}
+
+/// A future which will match the inner pattern and push back the read bytes.
+///
+/// This future is generally created by invoking
+/// `ReadFrom::read_from` method for `Peek` pattern.
+pub struct ReadPeek<R: Read, P, E: FromIoError = Error>(P::Future)
+where
+    P: AsyncMatch<PatternReader<R, E>>;
+impl<R: Read, E: FromIoError, P> Future for ReadPeek<R, P, E>
+where
+    P: AsyncMatch<PatternReader<R, E>>,
+{
+    type Item = (PatternReader<R, E>, P::Value);
+    type Error = AsyncError<PatternReader<R, E>, E>;
+    fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
+        match self.0.poll() {
+            Err(e) => {
+                Err(e.map_state(|mut r| {
+                    r.rewind();
+                    r
+                }))
+            }
+            Ok(Async::Ready((mut r, v))) => {
+                r.rewind();
+                Ok(Async::Ready((r, v)))
+            }
+            Ok(Async::NotReady) => Ok(Async::NotReady),
+        }
+    }
+}
+impl<R: Read, E: FromIoError, P> AsyncMatch<PatternReader<R, E>> for read::Peek<P>
+where
+    P: AsyncMatch<PatternReader<R, E>>,
+{
+    type Future = ReadPeek<R, P, E>;
+    fn async_match(self, mut matcher: PatternReader<R, E>) -> Self::Future {
+        matcher.mark();
+        ReadPeek(self.0.async_match(matcher))
+    }
+}
+
+/// A future which will match the inner pattern and compute the checksum of the read bytes.
+///
+/// This future is generally created by invoking
+/// `ReadFrom::read_from` method for `Checksummed` pattern.
+pub struct ReadChecksummed<R: Read, P, H, E: FromIoError = Error>(P::Future, PhantomData<H>)
+where
+    P: AsyncMatch<PatternReader<R, E>>;
+impl<R: Read, E: FromIoError, P, H> Future for ReadChecksummed<R, P, H, E>
+where
+    P: AsyncMatch<PatternReader<R, E>>,
+    H: Checksum + Send + 'static,
+{
+    type Item = (PatternReader<R, E>, (P::Value, H::Digest));
+    type Error = AsyncError<PatternReader<R, E>, E>;
+    fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
+        match self.0.poll() {
+            Err(e) => {
+                Err(e.map_state(|mut r| {
+                    r.finish_checksum::<H>();
+                    r
+                }))
+            }
+            Ok(Async::Ready((mut r, v))) => {
+                let digest = r.finish_checksum::<H>().digest();
+                Ok(Async::Ready((r, (v, digest))))
+            }
+            Ok(Async::NotReady) => Ok(Async::NotReady),
+        }
+    }
+}
+impl<R: Read, E: FromIoError, P, H> AsyncMatch<PatternReader<R, E>> for Checksummed<P, H>
+where
+    P: AsyncMatch<PatternReader<R, E>>,
+    H: Checksum + Send + 'static,
+{
+    type Future = ReadChecksummed<R, P, H, E>;
+    fn async_match(self, mut matcher: PatternReader<R, E>) -> Self::Future {
+        matcher.start_checksum::<H>();
+        ReadChecksummed(self.unwrap().async_match(matcher), PhantomData)
+    }
+}
+
+impl<R: Read + Seek, E: FromIoError> AsyncMatch<PatternReader<R, E>> for read::SeekTo {
+    type Future = futures::Done<(PatternReader<R, E>, u64), AsyncError<PatternReader<R, E>, E>>;
+    fn async_match(self, mut matcher: PatternReader<R, E>) -> Self::Future {
+        match matcher.seek(self.0) {
+            Ok(position) => futures::done(Ok((matcher, position))),
+            Err(e) => futures::done(Err(AsyncError::new(matcher, E::from(e)))),
+        }
+    }
+}
+
+impl<R: Read, E: FromIoError> AsyncMatch<PatternReader<R, E>> for read::Position {
+    type Future = futures::Done<(PatternReader<R, E>, u64), AsyncError<PatternReader<R, E>, E>>;
+    fn async_match(self, matcher: PatternReader<R, E>) -> Self::Future {
+        let position = matcher.position();
+        futures::done(Ok((matcher, position)))
+    }
+}
+
+impl<R: Read, S, E: FromIoError, F, V> AsyncMatch<PatternReader<Stateful<R, S>, E>> for read::State<F, S>
+where
+    F: FnOnce(&mut S) -> V,
+{
+    type Future = futures::Done<(PatternReader<Stateful<R, S>, E>, V),
+                                AsyncError<PatternReader<Stateful<R, S>, E>, E>>;
+    fn async_match(self, mut matcher: PatternReader<Stateful<R, S>, E>) -> Self::Future {
+        let v = (self.unwrap())(&mut matcher.inner_mut().state);
+        futures::done(Ok((matcher, v)))
+    }
+}
+
+/// A future which will match the inner pattern and
+/// apply a function to the state of the stream and the value.
+///
+/// This future is generally created by invoking
+/// `ReadFrom::read_from` method for `WithState` pattern.
+pub struct ReadWithState<P, F>(Option<(P, F)>);
+impl<R, S, E, P, F, V, U> Future for ReadWithState<P, F>
+where
+    P: Future<Item = (PatternReader<Stateful<R, S>, E>, V)>,
+    F: FnOnce(&mut S, V) -> U,
+{
+    type Item = (PatternReader<Stateful<R, S>, E>, U);
+    type Error = P::Error;
+    fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
+        let (mut p, f) = self.0.take().expect("Cannot poll ReadWithState twice");
+        if let Async::Ready((mut matcher, v)) = p.poll()? {
+            let u = f(&mut matcher.inner_mut().state, v);
+            Ok(Async::Ready((matcher, u)))
+        } else {
+            self.0 = Some((p, f));
+            Ok(Async::NotReady)
+        }
+    }
+}
+impl<R: Read, S, E: FromIoError, P, F, U> AsyncMatch<PatternReader<Stateful<R, S>, E>> for WithState<P, F, S>
+where
+    P: AsyncMatch<PatternReader<Stateful<R, S>, E>>,
+    F: FnOnce(&mut S, P::Value) -> U,
+{
+    type Future = ReadWithState<P::Future, F>;
+    fn async_match(self, matcher: PatternReader<Stateful<R, S>, E>) -> Self::Future {
+        let (p, f) = self.unwrap();
+        ReadWithState(Some((p.async_match(matcher), f)))
+    }
+}
+
+/// A future which will match the inner pattern at the specified offset.
+///
+/// This future is generally created by invoking
+/// `ReadFrom::read_from` method for `At` pattern.
+pub struct ReadAt<R: Read, P, E: FromIoError = Error>(Option<SeekResult<P::Future, PatternReader<R, E>, E>>)
+where
+    P: AsyncMatch<PatternReader<R, E>>;
+impl<R: Read + Seek, E: FromIoError, P> Future for ReadAt<R, P, E>
+where
+    P: AsyncMatch<PatternReader<R, E>>,
+{
+    type Item = (PatternReader<R, E>, P::Value);
+    type Error = AsyncError<PatternReader<R, E>, E>;
+    fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
+        let (mut f, restore) = self.0.take().expect("Cannot poll ReadAt twice")?;
+        match f.poll() {
+            Err(e) => {
+                let mut seek_error = None;
+                let e = e.map_state(|mut r| {
+                    seek_error = r.seek(SeekFrom::Start(restore)).err();
+                    r
+                });
+                if let Some(seek_error) = seek_error {
+                    Err(e.map_error(|e| seek_back_error(e, seek_error)))
+                } else {
+                    Err(e)
+                }
+            }
+            Ok(Async::Ready((mut r, v))) => {
+                if let Err(e) = r.seek(SeekFrom::Start(restore)) {
+                    return Err(AsyncError::new(r, E::from(e)));
+                }
+                Ok(Async::Ready((r, v)))
+            }
+            Ok(Async::NotReady) => {
+                self.0 = Some(Ok((f, restore)));
+                Ok(Async::NotReady)
+            }
+        }
+    }
+}
+impl<R: Read + Seek, E: FromIoError, P> AsyncMatch<PatternReader<R, E>> for read::At<P>
+where
+    P: AsyncMatch<PatternReader<R, E>>,
+{
+    type Future = ReadAt<R, P, E>;
+    fn async_match(self, mut matcher: PatternReader<R, E>) -> Self::Future {
+        let result = matcher
+            .stream_position()
+            .and_then(|restore| matcher.seek(SeekFrom::Start(self.0)).map(|_| restore));
+        match result {
+            Ok(restore) => ReadAt(Some(Ok((self.1.async_match(matcher), restore)))),
+            Err(e) => ReadAt(Some(Err(AsyncError::new(matcher, E::from(e))))),
+        }
+    }
+}
+
+
+enum Repeat<A, B> {
+    Item(A),
+    Delimiter(B),
+    Polled,
+}
+
+/// Ends the mark which was started for looking ahead, keeping the bytes read since then consumed.
+fn release<R, E>(mut reader: PatternReader<R, E>) -> PatternReader<R, E> {
+    reader.release();
+    reader
+}
+
+fn too_many_items<R, E>(reader: PatternReader<R, E>, max_items: usize) -> AsyncError<PatternReader<R, E>, E>
+where
+    E: FromIoError,
+{
+    let message = format!("Too many items (limit: {})", max_items);
+    AsyncError::new(reader, E::from(Error::new(ErrorKind::InvalidData, message)))
+}
+
+/// A future which will collect the values of a pattern repeated a number of times.
+///
+/// This future is generally created by invoking
+/// `ReadFrom::read_from` method for `Many` pattern.
+pub struct ReadMany<R: Read, P, C, E: FromIoError = Error>
+where
+    P: AsyncMatch<PatternReader<R, E>>,
+{
+    phase: ManyPhase<P::Future, PatternReader<R, E>>,
+    pattern: P,
+    min: usize,
+    max: usize,
+    values: Vec<P::Value>,
+    _collection: std::marker::PhantomData<C>,
+}
+enum ManyPhase<F, R> {
+    Item(F),
+    Done(R),
+    Polled,
+}
+impl<R: Read, E: FromIoError, P, C> ReadMany<R, P, C, E>
+where
+    P: AsyncMatch<PatternReader<R, E>> + Clone,
+{
+    fn next_item(&mut self, mut reader: PatternReader<R, E>) {
+        if self.values.len() == self.max {
+            self.phase = ManyPhase::Done(reader);
+        } else {
+            if self.values.len() >= self.min {
+                // The items beyond `min` are optional, so the bytes of a mismatched one
+                // have to be pushed back.
+                reader.mark();
+            }
+            self.phase = ManyPhase::Item(self.pattern.clone().async_match(reader));
+        }
+    }
+}
+impl<R: Read, E: FromIoError, P, C> Future for ReadMany<R, P, C, E>
+where
+    E: MatchError,
+    P: AsyncMatch<PatternReader<R, E>> + Clone,
+    C: std::iter::FromIterator<P::Value>,
+{
+    type Item = (PatternReader<R, E>, C);
+    type Error = AsyncError<PatternReader<R, E>, E>;
+    fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
+        loop {
+            match std::mem::replace(&mut self.phase, ManyPhase::Polled) {
+                ManyPhase::Item(mut f) => {
+                    let is_optional = self.values.len() >= self.min;
+                    match f.poll() {
+                        Err(e) => {
+                            if !is_optional {
+                                return Err(e);
+                            }
+                            if !e.error_ref().is_mismatch() {
+                                return Err(e.map_state(release));
+                            }
+                            let (mut r, _) = e.unwrap();
+                            r.rewind();
+                            self.phase = ManyPhase::Done(r);
+                        }
+                        Ok(Async::Ready((mut r, v))) => {
+                            if is_optional {
+                                r.release();
+                            }
+                            self.values.push(v);
+                            self.next_item(r);
+                        }
+                        Ok(Async::NotReady) => {
+                            self.phase = ManyPhase::Item(f);
+                            return Ok(Async::NotReady);
+                        }
+                    }
+                }
+                ManyPhase::Done(r) => {
+                    let values = std::mem::take(&mut self.values);
+                    return Ok(Async::Ready((r, values.into_iter().collect())));
+                }
+                ManyPhase::Polled => panic!("Cannot poll ReadMany twice"),
+            }
+        }
+    }
+}
+impl<R: Read, E: FromIoError, P, C> AsyncMatch<PatternReader<R, E>> for combinators::Many<P, C>
+where
+    E: MatchError,
+    P: AsyncMatch<PatternReader<R, E>> + Clone,
+    C: std::iter::FromIterator<P::Value>,
+{
+    type Future = ReadMany<R, P, C, E>;
+    fn async_match(self, matcher: PatternReader<R, E>) -> Self::Future {
+        let (pattern, min, max) = self.unwrap();
+        let mut future = ReadMany {
+            phase: ManyPhase::Polled,
+            pattern,
+            min,
+            max,
+            values: Vec::new(),
+            _collection: std::marker::PhantomData,
+        };
+        future.next_item(matcher);
+        future
+    }
+}
+
+/// A future which will collect the values of a pattern separated by another pattern.
+///
+/// This future is generally created by invoking
+/// `ReadFrom::read_from` method for `SepBy` pattern.
+pub struct ReadSepBy<R: Read, P, S, C, E: FromIoError = Error>
+where
+    P: AsyncMatch<PatternReader<R, E>>,
+    S: AsyncMatch<PatternReader<R, E>>,
+{
+    phase: Repeat<P::Future, S::Future>,
+    pattern: P,
+    sep: S,
+    max_items: usize,
+    values: Vec<P::Value>,
+    _collection: std::marker::PhantomData<C>,
+}
+impl<R: Read, E: FromIoError, P, S, C> Future for ReadSepBy<R, P, S, C, E>
+where
+    E: MatchError,
+    P: AsyncMatch<PatternReader<R, E>> + Clone,
+    S: AsyncMatch<PatternReader<R, E>> + Clone,
+    C: std::iter::FromIterator<P::Value>,
+{
+    type Item = (PatternReader<R, E>, C);
+    type Error = AsyncError<PatternReader<R, E>, E>;
+    fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
+        loop {
+            let mut r = match std::mem::replace(&mut self.phase, Repeat::Polled) {
+                Repeat::Item(mut f) => {
+                    let is_first = self.values.is_empty();
+                    match f.poll() {
+                        Err(e) => {
+                            if !is_first {
+                                return Err(e);
+                            }
+                            if !e.error_ref().is_mismatch() {
+                                return Err(e.map_state(release));
+                            }
+                            let (mut r, _) = e.unwrap();
+                            r.rewind();
+                            return Ok(Async::Ready((r, C::from_iter(Vec::new()))));
+                        }
+                        Ok(Async::Ready((mut r, v))) => {
+                            if is_first {
+                                r.release();
+                            }
+                            self.values.push(v);
+                            r
+                        }
+                        Ok(Async::NotReady) => {
+                            self.phase = Repeat::Item(f);
+                            return Ok(Async::NotReady);
+                        }
+                    }
+                }
+                Repeat::Delimiter(mut f) => {
+                    match f.poll() {
+                        Err(e) => {
+                            if !e.error_ref().is_mismatch() {
+                                return Err(e.map_state(release));
+                            }
+                            let (mut r, _) = e.unwrap();
+                            r.rewind();
+                            let values = std::mem::take(&mut self.values);
+                            return Ok(Async::Ready((r, values.into_iter().collect())));
+                        }
+                        Ok(Async::Ready((mut r, _))) => {
+                            r.release();
+                            if self.values.len() == self.max_items {
+                                return Err(too_many_items(r, self.max_items));
+                            }
+                            self.phase = Repeat::Item(self.pattern.clone().async_match(r));
+                            continue;
+                        }
+                        Ok(Async::NotReady) => {
+                            self.phase = Repeat::Delimiter(f);
+                            return Ok(Async::NotReady);
+                        }
+                    }
+                }
+                Repeat::Polled => panic!("Cannot poll ReadSepBy twice"),
+            };
+            r.mark();
+            self.phase = Repeat::Delimiter(self.sep.clone().async_match(r));
+        }
+    }
+}
+impl<R: Read, E: FromIoError, P, S, C> AsyncMatch<PatternReader<R, E>> for combinators::SepBy<P, S, C>
+where
+    E: MatchError,
+    P: AsyncMatch<PatternReader<R, E>> + Clone,
+    S: AsyncMatch<PatternReader<R, E>> + Clone,
+    C: std::iter::FromIterator<P::Value>,
+{
+    type Future = ReadSepBy<R, P, S, C, E>;
+    fn async_match(self, mut matcher: PatternReader<R, E>) -> Self::Future {
+        let (pattern, sep, max_items) = self.unwrap();
+        matcher.mark();
+        ReadSepBy {
+            phase: Repeat::Item(pattern.clone().async_match(matcher)),
+            pattern,
+            sep,
+            max_items,
+            values: Vec::new(),
+            _collection: std::marker::PhantomData,
+        }
+    }
+}
+
+/// A future which will collect the values of a pattern until a terminator pattern is matched.
+///
+/// This future is generally created by invoking
+/// `ReadFrom::read_from` method for `TerminatedBy` pattern.
+pub struct ReadTerminatedBy<R: Read, P, T, C, E: FromIoError = Error>
+where
+    P: AsyncMatch<PatternReader<R, E>>,
+    T: AsyncMatch<PatternReader<R, E>>,
+{
+    phase: Repeat<P::Future, T::Future>,
+    pattern: P,
+    end: T,
+    max_items: usize,
+    values: Vec<P::Value>,
+    _collection: std::marker::PhantomData<C>,
+}
+impl<R: Read, E: FromIoError, P, T, C> Future for ReadTerminatedBy<R, P, T, C, E>
+where
+    E: MatchError,
+    P: AsyncMatch<PatternReader<R, E>> + Clone,
+    T: AsyncMatch<PatternReader<R, E>> + Clone,
+    C: std::iter::FromIterator<P::Value>,
+{
+    type Item = (PatternReader<R, E>, C);
+    type Error = AsyncError<PatternReader<R, E>, E>;
+    fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
+        loop {
+            match std::mem::replace(&mut self.phase, Repeat::Polled) {
+                Repeat::Item(mut f) => {
+                    match f.poll() {
+                        Err(e) => return Err(e),
+                        Ok(Async::Ready((mut r, v))) => {
+                            self.values.push(v);
+                            r.mark();
+                            self.phase = Repeat::Delimiter(self.end.clone().async_match(r));
+                        }
+                        Ok(Async::NotReady) => {
+                            self.phase = Repeat::Item(f);
+                            return Ok(Async::NotReady);
+                        }
+                    }
+                }
+                Repeat::Delimiter(mut f) => {
+                    match f.poll() {
+                        Err(e) => {
+                            if !e.error_ref().is_mismatch() {
+                                return Err(e.map_state(release));
+                            }
+                            let (mut r, _) = e.unwrap();
+                            r.rewind();
+                            if self.values.len() == self.max_items {
+                                return Err(too_many_items(r, self.max_items));
+                            }
+                            self.phase = Repeat::Item(self.pattern.clone().async_match(r));
+                        }
+                        Ok(Async::Ready((mut r, _))) => {
+                            r.release();
+                            let values = std::mem::take(&mut self.values);
+                            return Ok(Async::Ready((r, values.into_iter().collect())));
+                        }
+                        Ok(Async::NotReady) => {
+                            self.phase = Repeat::Delimiter(f);
+                            return Ok(Async::NotReady);
+                        }
+                    }
+                }
+                Repeat::Polled => panic!("Cannot poll ReadTerminatedBy twice"),
+            }
+        }
+    }
+}
+impl<R: Read, E: FromIoError, P, T, C> AsyncMatch<PatternReader<R, E>> for combinators::TerminatedBy<P, T, C>
+where
+    E: MatchError,
+    P: AsyncMatch<PatternReader<R, E>> + Clone,
+    T: AsyncMatch<PatternReader<R, E>> + Clone,
+    C: std::iter::FromIterator<P::Value>,
+{
+    type Future = ReadTerminatedBy<R, P, T, C, E>;
+    fn async_match(self, mut matcher: PatternReader<R, E>) -> Self::Future {
+        let (pattern, end, max_items) = self.unwrap();
+        matcher.mark();
+        ReadTerminatedBy {
+            phase: Repeat::Delimiter(end.clone().async_match(matcher)),
+            pattern,
+            end,
+            max_items,
+            values: Vec::new(),
+            _collection: std::marker::PhantomData,
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
     use std::io;
@@ -764,6 +1747,211 @@ mod test {
     use pattern::{self, Pattern};
     use super::*;
 
+    /// A reader which returns the `WouldBlock` error on every other read.
+    struct WouldBlockEveryOther<'a>(&'a [u8], bool);
+    impl<'a> io::Read for WouldBlockEveryOther<'a> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            self.1 = !self.1;
+            if self.1 {
+                ::futures::task::current().notify();
+                Err(io::Error::new(io::ErrorKind::WouldBlock, "Would block"))
+            } else {
+                self.0.read(buf)
+            }
+        }
+    }
+
+    #[test]
+    fn tuple_keeps_values_when_polled_after_not_ready() {
+        use pattern::read::U8;
+
+        let input = [0, 1, 2, 3, 4];
+        let pattern = (U8, U8, U8, U8, U8);
+        let reader = WouldBlockEveryOther(&input[..], false);
+        let (_, values) = pattern.read_from(reader).wait().unwrap();
+        assert_eq!(values, (0, 1, 2, 3, 4));
+    }
+
+    #[test]
+    fn many_returns_io_errors_after_min_items() {
+        use pattern::read::U8;
+
+        let reader = io::Read::chain(&[0, 1][..], FailingReader);
+        let e = U8.many(1, 5).read_from(reader).wait().err().unwrap();
+        assert_eq!(e.error_ref().kind(), io::ErrorKind::Other);
+
+        let (_, values) = U8.many(1, 5).read_from(&[0, 1][..]).wait().unwrap();
+        assert_eq!(values, [0, 1]);
+    }
+
+    #[test]
+    fn many_pushes_back_bytes_of_mismatched_item() {
+        use pattern::Endian;
+        use pattern::read::{U8, U16, Eos};
+
+        let pattern = (U8.expect_eq(0).many(0, 4), U8);
+        let (_, (zeros, next)) = pattern.read_from(&[0, 0, 1][..]).wait().unwrap();
+        assert_eq!(zeros, [0, 0]);
+        assert_eq!(next, 1);
+
+        // A truncated trailing item is left to the following pattern.
+        let pattern = (U16.be().many(1, 4), U8);
+        let (_, (values, rest)) = pattern.clone().read_from(&[0, 1, 2][..]).wait().unwrap();
+        assert_eq!(values, [1]);
+        assert_eq!(rest, 2);
+        let (_, (_, eos)) = (pattern.0, Eos).read_from(&[0, 1, 2][..]).wait().unwrap();
+        assert_eq!(eos, Err(2));
+    }
+
+    #[test]
+    fn sep_by_returns_io_errors_of_first_item() {
+        use pattern::read::U8;
+
+        let pattern = U8.sep_by(U8.expect_eq(b','));
+        let e = pattern.clone().read_from(FailingReader).wait().err().unwrap();
+        assert_eq!(e.error_ref().kind(), io::ErrorKind::Other);
+
+        let (_, values) = pattern.read_from(&b""[..]).wait().unwrap();
+        assert!(values.is_empty());
+    }
+
+    #[test]
+    fn sep_by_and_terminated_by_end_marks_on_errors() {
+        use pattern::read::U8;
+
+        let pattern = U8.sep_by(U8.expect_eq(b','));
+        let e = pattern.clone().read_from_buffered(FailingReader).wait().err().unwrap();
+        assert!(e.state_ref().marks.is_empty());
+
+        let reader = io::Read::chain(&[1][..], FailingReader);
+        let e = pattern.read_from_buffered(reader).wait().err().unwrap();
+        assert_eq!(e.error_ref().kind(), io::ErrorKind::Other);
+        assert!(e.state_ref().marks.is_empty());
+
+        let pattern = U8.terminated_by(U8.expect_eq(0));
+        let e = pattern.read_from_buffered(FailingReader).wait().err().unwrap();
+        assert!(e.state_ref().marks.is_empty());
+    }
+
+    #[test]
+    fn at_reports_seek_back_errors() {
+        use std::error::Error as StdError;
+        use io::SeekBackError;
+        use pattern::Endian;
+        use pattern::read::{At, U16};
+
+        /// A cursor which fails to seek back to the start.
+        struct NoRewind(io::Cursor<Vec<u8>>);
+        impl io::Read for NoRewind {
+            fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+                self.0.read(buf)
+            }
+        }
+        impl io::Seek for NoRewind {
+            fn seek(&mut self, pos: io::SeekFrom) -> io::Result<u64> {
+                if let io::SeekFrom::Start(0) = pos {
+                    return Err(io::Error::new(io::ErrorKind::Other, "Cannot rewind"));
+                }
+                self.0.seek(pos)
+            }
+        }
+
+        let reader = NoRewind(io::Cursor::new(vec![0, 1, 2]));
+        let e = At(2, U16.be()).read_from(reader).wait().err().unwrap().into_error();
+        assert_eq!(e.kind(), io::ErrorKind::Other);
+
+        let inner = e.get_ref().unwrap().downcast_ref::<SeekBackError>().unwrap();
+        assert_eq!(inner.source().unwrap().to_string(), "Cannot rewind");
+        assert_eq!(inner.original(), "Unexpected Eof (1 bytes are required)");
+    }
+
+    #[test]
+    fn scan_reads_many_chunks_without_recursion() {
+        use pattern::read::{Scan, U8};
+
+        let mut input = vec![b'a'; 1024 * 1024];
+        input.extend_from_slice(b";b");
+        let pattern = (Scan::new(b';').chunk_size(0), U8);
+        let (_, (bytes, rest)) = pattern.read_from(&input[..]).wait().unwrap();
+        assert_eq!(bytes.len(), 1024 * 1024 + 1);
+        assert_eq!(rest, b'b');
+    }
+
+    #[test]
+    fn scan_leaves_bytes_after_end_for_next_read() {
+        use pattern::read::Scan;
+
+        let (rest, first) = Scan::new(b';').read_from_buffered(&b"foo;bar;"[..]).wait().unwrap();
+        assert_eq!(first, b"foo;");
+        let (rest, second) = Scan::new(b';').read_from_buffered(rest).wait().unwrap();
+        assert_eq!(second, b"bar;");
+        assert!(Scan::new(b';').read_from(rest).wait().is_err());
+    }
+
+    #[test]
+    fn fixed_reads_arrays_of_zero_sized_patterns() {
+        use pattern::read::{Fixed, U8};
+
+        let pattern = Fixed(([[], [], []] as [[U8; 0]; 3], U8));
+        let (_, (empty, value)) = pattern.read_from(&[7][..]).wait().unwrap();
+        assert_eq!(empty, [[], [], []]);
+        assert_eq!(value, 7);
+    }
+
+    #[test]
+    fn state_is_shared_across_patterns() {
+        use io::Stateful;
+        use pattern::Branch;
+        use pattern::read::{U8, Utf8};
+
+        // A name is either a literal (`0, len, bytes..`), which is appended to the table,
+        // or a reference (`1, index`) to a name read before.
+        let name = || {
+            U8.and_then(|tag| if tag == 0 {
+                let literal = Utf8(U8.and_then(|n| vec![0; n as usize]));
+                Branch::<_, _>::A(literal.with_state(|names: &mut Vec<String>, name: String| {
+                    names.push(name.clone());
+                    name
+                }))
+            } else {
+                Branch::B(U8.with_state(|names: &mut Vec<String>, i| names[i as usize].clone()))
+            })
+        };
+        let input = [0, 3, b'f', b'o', b'o', 0, 3, b'b', b'a', b'r', 1, 0, 1, 1];
+        let stream = Stateful { stream: &input[..], state: Vec::new() };
+        let pattern = (name(), name(), name(), name());
+        let (r, names) = pattern.read_from(stream).wait().unwrap();
+        assert_eq!(names.0, "foo");
+        assert_eq!(names.1, "bar");
+        assert_eq!(names.2, "foo");
+        assert_eq!(names.3, "bar");
+        assert_eq!(r.state, ["foo", "bar"]);
+    }
+
+    #[test]
+    fn looked_ahead_bytes_are_left_in_returned_reader() {
+        use std::io::Read;
+        use pattern::read::{U8, Peek};
+
+        let (mut rest, value) = Peek(U8).read_from_buffered(&b"xyz"[..]).wait().unwrap();
+        assert_eq!(value, b'x');
+        let mut buf = Vec::new();
+        rest.read_to_end(&mut buf).unwrap();
+        assert_eq!(buf, b"xyz");
+
+        let (rest, _) = (U8, Peek(U8)).read_from_buffered(&b"xyz"[..]).wait().unwrap();
+        let (_, value) = U8.read_from(rest).wait().unwrap();
+        assert_eq!(value, b'y');
+    }
+
+    /// A reader which always fails.
+    struct FailingReader;
+    impl io::Read for FailingReader {
+        fn read(&mut self, _buf: &mut [u8]) -> io::Result<usize> {
+            Err(io::Error::new(io::ErrorKind::Other, "Broken"))
+        }
+    }
+
     #[test]
     fn it_works() {
         assert_eq!(().and_then(|_| ())
@@ -778,4 +1966,46 @@ mod test {
         assert_eq!(pattern.read_from(io::Cursor::new(vec![])).wait().unwrap().1,
                    3);
     }
+
+    #[test]
+    fn sixteen_element_tuple_works() {
+        use io::ExternalSize;
+        use pattern::read::U8;
+
+        let pattern = (U8, U8, U8, U8, U8, U8, U8, U8, U8, U8, U8, U8, U8, U8, U8, U8);
+        assert_eq!(pattern.external_size(), 16);
+
+        let input = (0..17).collect::<Vec<u8>>();
+        let (rest, values) = pattern.read_from(io::Cursor::new(input)).wait().unwrap();
+        assert_eq!((values.0, values.7, values.15), (0, 7, 15));
+        assert_eq!(rest.position(), 16);
+    }
+
+    #[test]
+    fn checksum_ignores_rewound_bytes() {
+        use checksum::{Checksum, Adler32};
+        use pattern::read::{U8, Peek};
+
+        let input = vec![1, 2, 3, 4];
+        let pattern = (Peek((U8, U8)), U8, U8).checksummed::<Adler32>();
+        let pattern = (pattern, Peek(U8).checksummed::<Adler32>(), U8);
+        let (_, ((_, digest), (_, empty), _)) = pattern
+            .read_from(io::Cursor::new(input))
+            .wait()
+            .unwrap();
+        assert_eq!(digest, Adler32::of(&[1, 2]));
+        assert_eq!(empty, Adler32::of(&[]));
+    }
+
+    #[test]
+    fn seek_discards_looked_ahead_bytes() {
+        use std::io::SeekFrom;
+        use pattern::read::{U8, Peek, SeekTo};
+
+        let pattern = (U8, Peek((U8, U8)), SeekTo(SeekFrom::Current(1)), U8);
+        let input = io::Cursor::new(vec![0, 1, 2, 3]);
+        let (r, values) = pattern.read_from(input).wait().unwrap();
+        assert_eq!(values, (0, (1, 2), 2, 2));
+        assert_eq!(r.position(), 3);
+    }
 }
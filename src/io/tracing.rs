@@ -0,0 +1,470 @@
+use std::fmt;
+use std::io::{Read, Write, Error};
+use std::marker::PhantomData;
+use futures::{Poll, Async, Future};
+
+use pattern::{Pattern, Buf, Window};
+use pattern::read;
+use pattern::write::{self, U24, I24, U40, I40, U48, I48, U56, I56};
+use pattern::combinators::{BE, LE, PartialBuf};
+use matcher::{AsyncMatch, Matcher, TrackPosition};
+use super::{PatternReader, PatternWriter, AsyncIoError};
+use super::misc::Counter;
+
+/// An entry of the trace recorded by [`TracingReader`](./struct.TracingReader.html) and
+/// [`TracingWriter`](./struct.TracingWriter.html).
+///
+/// Each entry corresponds to a primitive pattern evaluated by the matcher.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TraceEntry {
+    /// The name of the pattern type (e.g., `"BE<read::U16>"`).
+    pub pattern: &'static str,
+
+    /// The byte offset at which the matching was started.
+    pub offset: usize,
+
+    /// The byte size consumed (or produced) by the matching.
+    pub size: usize,
+
+    /// The debug representation of the matched value.
+    ///
+    /// For writing patterns, this is the representation of the pattern itself.
+    /// Representations longer than 64 bytes are truncated and end with `"..."`,
+    /// so large buffers are not copied into the trace.
+    ///
+    /// If the matching failed, this will be `None`.
+    pub value: Option<String>,
+}
+impl fmt::Display for TraceEntry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "[{}..{}] {}", self.offset, self.offset + self.size, self.pattern)?;
+        if let Some(ref value) = self.value {
+            write!(f, " => {}", value)
+        } else {
+            write!(f, " => <error>")
+        }
+    }
+}
+
+/// A matcher which reads patterns from `R` like `PatternReader`,
+/// and records every primitive pattern evaluated.
+///
+/// Combinators (e.g., tuples, `Map` and `AndThen`) are transparent to this matcher,
+/// so only the primitive patterns which actually read bytes will appear in the trace.
+///
+/// The patterns which require lookahead (i.e., `read::Peek`, `Pattern::many`,
+/// `Pattern::sep_by` and `Pattern::terminated_by`) are not supported by this matcher,
+/// because the bytes recorded in the trace cannot be pushed back.
+///
+/// If the `log` feature is enabled, each entry is also emitted via the `log` crate
+/// (`trace` level for successes and `debug` level for failures).
+///
+/// # Examples
+///
+/// ```
+/// # extern crate futures;
+/// # extern crate handy_async;
+/// use futures::Future;
+/// use handy_async::io::TracingReader;
+/// use handy_async::matcher::AsyncMatch;
+/// use handy_async::pattern::{Pattern, Endian};
+/// use handy_async::pattern::read::{U8, U16};
+///
+/// # fn main() {
+/// let reader = TracingReader::new(&[1, 0, 2, 3][..]);
+/// let e = (U8, U16.be(), U16.be()).async_match(reader).wait().err().unwrap();
+///
+/// // Dumps the trace when an error occurred.
+/// let trace = e.state_ref().trace();
+/// assert_eq!(trace.len(), 3);
+/// assert_eq!(trace[1].to_string(), "[1..3] BE<read::U16> => 2");
+/// assert_eq!(trace[2].to_string(), "[3..4] BE<read::U16> => <error>");
+/// # }
+/// ```
+pub struct TracingReader<R> {
+    inner: PatternReader<Counter<R>>,
+    trace: Vec<TraceEntry>,
+}
+impl<R: Read> TracingReader<R> {
+    /// Makes new `TracingReader` instance.
+    pub fn new(inner: R) -> Self {
+        TracingReader {
+            inner: PatternReader::new(Counter::new(inner)),
+            trace: Vec::new(),
+        }
+    }
+}
+impl<R> TracingReader<R> {
+    /// Returns the entries recorded so far.
+    pub fn trace(&self) -> &[TraceEntry] {
+        &self.trace
+    }
+
+    /// Takes the entries recorded so far, leaving an empty trace in its place.
+    pub fn take_trace(&mut self) -> Vec<TraceEntry> {
+        ::std::mem::take(&mut self.trace)
+    }
+
+    /// Returns the total byte size read from the inner reader.
+    pub fn offset(&self) -> usize {
+        self.inner.inner_ref().read_size()
+    }
+
+    /// Returns the reference to the inner reader.
+    pub fn inner_ref(&self) -> &R {
+        self.inner.inner_ref().inner_ref()
+    }
+
+    /// Returns the mutable reference to the inner reader.
+    pub fn inner_mut(&mut self) -> &mut R {
+        self.inner.inner_mut().inner_mut()
+    }
+
+    /// Unwraps this `TracingReader`, returning the inner reader.
+    pub fn into_inner(self) -> R {
+        self.inner.into_inner().into_inner()
+    }
+}
+impl<R> Matcher for TracingReader<R> {
+    type Error = Error;
+    fn position(&self) -> Option<u64> {
+        Some(self.offset() as u64)
+    }
+}
+impl<R> TrackPosition for TracingReader<R> {}
+
+/// A matcher which writes patterns into `W` like `PatternWriter`,
+/// and records every primitive pattern evaluated.
+///
+/// See the documentation of [`TracingReader`](./struct.TracingReader.html) for details.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate futures;
+/// # extern crate handy_async;
+/// use futures::Future;
+/// use handy_async::io::TracingWriter;
+/// use handy_async::matcher::AsyncMatch;
+/// use handy_async::pattern::Endian;
+///
+/// # fn main() {
+/// let writer = TracingWriter::new(Vec::new());
+/// let (writer, _) = (1u8, 2u16.be()).async_match(writer).wait().unwrap();
+/// assert_eq!(writer.trace()[1].to_string(), "[1..3] BE<u16> => BE(2)");
+/// assert_eq!(writer.into_inner(), [1, 0, 2]);
+/// # }
+/// ```
+pub struct TracingWriter<W> {
+    inner: PatternWriter<Counter<W>>,
+    trace: Vec<TraceEntry>,
+}
+impl<W: Write> TracingWriter<W> {
+    /// Makes new `TracingWriter` instance.
+    pub fn new(inner: W) -> Self {
+        TracingWriter {
+            inner: PatternWriter::new(Counter::new(inner)),
+            trace: Vec::new(),
+        }
+    }
+}
+impl<W> TracingWriter<W> {
+    /// Returns the entries recorded so far.
+    pub fn trace(&self) -> &[TraceEntry] {
+        &self.trace
+    }
+
+    /// Takes the entries recorded so far, leaving an empty trace in its place.
+    pub fn take_trace(&mut self) -> Vec<TraceEntry> {
+        ::std::mem::take(&mut self.trace)
+    }
+
+    /// Returns the total byte size written to the inner writer.
+    pub fn offset(&self) -> usize {
+        self.inner.inner_ref().written_size()
+    }
+
+    /// Returns the reference to the inner writer.
+    pub fn inner_ref(&self) -> &W {
+        self.inner.inner_ref().inner_ref()
+    }
+
+    /// Returns the mutable reference to the inner writer.
+    pub fn inner_mut(&mut self) -> &mut W {
+        self.inner.inner_mut().inner_mut()
+    }
+
+    /// Unwraps this `TracingWriter`, returning the inner writer.
+    pub fn into_inner(self) -> W {
+        self.inner.into_inner().into_inner()
+    }
+}
+impl<W> Matcher for TracingWriter<W> {
+    type Error = Error;
+    fn position(&self) -> Option<u64> {
+        Some(self.offset() as u64)
+    }
+}
+impl<W> TrackPosition for TracingWriter<W> {}
+
+/// Future to match a primitive pattern with
+/// [`TracingReader`](../struct.TracingReader.html) or
+/// [`TracingWriter`](../struct.TracingWriter.html).
+pub struct MatchTraced<M, I, F, T> {
+    future: F,
+    trace: Option<Vec<TraceEntry>>,
+    pattern: &'static str,
+    offset: usize,
+    value: Option<String>,
+    position: fn(&I) -> usize,
+    wrap: fn(I, Vec<TraceEntry>) -> M,
+    _phantom: PhantomData<T>,
+}
+impl<M, I, F, T> MatchTraced<M, I, F, T> {
+    fn record(&mut self, inner: I, value: Option<String>) -> M {
+        let entry = TraceEntry {
+            pattern: self.pattern,
+            offset: self.offset,
+            size: (self.position)(&inner) - self.offset,
+            value,
+        };
+        #[cfg(feature = "log")]
+        {
+            if entry.value.is_some() {
+                trace!("{}", entry);
+            } else {
+                debug!("{}", entry);
+            }
+        }
+        let mut trace = self.trace.take().expect("Cannot poll MatchTraced twice");
+        trace.push(entry);
+        (self.wrap)(inner, trace)
+    }
+}
+impl<M, I, F, T> Future for MatchTraced<M, I, F, T>
+where
+    F: Future<Item = (I, T), Error = AsyncIoError<I>>,
+    T: fmt::Debug,
+{
+    type Item = (M, T);
+    type Error = AsyncIoError<M>;
+    fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
+        match self.future.poll() {
+            Err(e) => {
+                let (inner, e) = e.unwrap();
+                let m = self.record(inner, None);
+                Err(AsyncIoError::new(m, e))
+            }
+            Ok(Async::Ready((inner, v))) => {
+                let value = self.value.take().unwrap_or_else(|| debug_capped(&v));
+                let m = self.record(inner, Some(value));
+                Ok(Async::Ready((m, v)))
+            }
+            Ok(Async::NotReady) => Ok(Async::NotReady),
+        }
+    }
+}
+
+const MAX_VALUE_LEN: usize = 64;
+
+/// Formats the debug representation of `value`, truncating it to `MAX_VALUE_LEN` bytes.
+///
+/// The formatting is aborted as soon as the limit is reached.
+fn debug_capped<T: fmt::Debug>(value: &T) -> String {
+    struct Capped(String, bool);
+    impl fmt::Write for Capped {
+        fn write_str(&mut self, s: &str) -> fmt::Result {
+            let room = MAX_VALUE_LEN - self.0.len();
+            if s.len() <= room {
+                self.0.push_str(s);
+                return Ok(());
+            }
+            let mut end = room;
+            while !s.is_char_boundary(end) {
+                end -= 1;
+            }
+            self.0.push_str(&s[..end]);
+            self.1 = true;
+            Err(fmt::Error)
+        }
+    }
+    let mut buf = Capped(String::new(), false);
+    let _ = fmt::write(&mut buf, format_args!("{:?}", value));
+    if buf.1 {
+        buf.0.push_str("...");
+    }
+    buf.0
+}
+
+fn read_position<R>(r: &PatternReader<Counter<R>>) -> usize {
+    r.inner_ref().read_size()
+}
+fn wrap_reader<R>(inner: PatternReader<Counter<R>>, trace: Vec<TraceEntry>) -> TracingReader<R> {
+    TracingReader {
+        inner,
+        trace,
+    }
+}
+fn write_position<W>(w: &PatternWriter<Counter<W>>) -> usize {
+    w.inner_ref().written_size()
+}
+fn wrap_writer<W>(inner: PatternWriter<Counter<W>>, trace: Vec<TraceEntry>) -> TracingWriter<W> {
+    TracingWriter {
+        inner,
+        trace,
+    }
+}
+
+macro_rules! impl_traced_read {
+    ($([$($g:ident),*] $pat:ty),*) => {
+        $(
+            impl<R: Read, $($g),*> AsyncMatch<TracingReader<R>> for $pat
+            where
+                $pat: AsyncMatch<PatternReader<Counter<R>>>,
+                <$pat as Pattern>::Value: fmt::Debug,
+            {
+                type Future = MatchTraced<
+                    TracingReader<R>,
+                    PatternReader<Counter<R>>,
+                    <$pat as AsyncMatch<PatternReader<Counter<R>>>>::Future,
+                    Self::Value,
+                >;
+                fn async_match(self, matcher: TracingReader<R>) -> Self::Future {
+                    let offset = matcher.offset();
+                    MatchTraced {
+                        future: self.async_match(matcher.inner),
+                        trace: Some(matcher.trace),
+                        pattern: stringify!($pat),
+                        offset,
+                        value: None,
+                        position: read_position,
+                        wrap: wrap_reader,
+                        _phantom: PhantomData,
+                    }
+                }
+            }
+        )*
+    }
+}
+impl_traced_read!(
+    [] read::U8, [] read::I8,
+    [] read::U16, [] BE<read::U16>, [] LE<read::U16>,
+    [] read::I16, [] BE<read::I16>, [] LE<read::I16>,
+    [] read::U24, [] BE<read::U24>, [] LE<read::U24>,
+    [] read::I24, [] BE<read::I24>, [] LE<read::I24>,
+    [] read::U32, [] BE<read::U32>, [] LE<read::U32>,
+    [] read::I32, [] BE<read::I32>, [] LE<read::I32>,
+    [] read::U40, [] BE<read::U40>, [] LE<read::U40>,
+    [] read::I40, [] BE<read::I40>, [] LE<read::I40>,
+    [] read::U48, [] BE<read::U48>, [] LE<read::U48>,
+    [] read::I48, [] BE<read::I48>, [] LE<read::I48>,
+    [] read::U56, [] BE<read::U56>, [] LE<read::U56>,
+    [] read::I56, [] BE<read::I56>, [] LE<read::I56>,
+    [] read::U64, [] BE<read::U64>, [] LE<read::U64>,
+    [] read::I64, [] BE<read::I64>, [] LE<read::I64>,
+    [] read::F32, [] BE<read::F32>, [] LE<read::F32>,
+    [] read::F64, [] BE<read::F64>, [] LE<read::F64>,
+    [] read::Eos, [] read::Line, [] read::All,
+    [] read::Skip, [] read::Align,
+    [F, T] read::Until<F, T>,
+    [S] read::Scan<S>,
+    [P] read::LengthPrefixedBytes<P>,
+    [P] read::Utf8<P>,
+    [P] read::Fixed<P>,
+    [] Vec<u8>, [] String,
+    [B] Buf<B>, [B] Window<B>, [B] PartialBuf<B>
+);
+
+macro_rules! impl_traced_write {
+    ($([$($g:ident),*] $pat:ty),*) => {
+        $(
+            impl<W: Write, $($g),*> AsyncMatch<TracingWriter<W>> for $pat
+            where
+                $pat: AsyncMatch<PatternWriter<Counter<W>>> + fmt::Debug,
+                <$pat as Pattern>::Value: fmt::Debug,
+            {
+                type Future = MatchTraced<
+                    TracingWriter<W>,
+                    PatternWriter<Counter<W>>,
+                    <$pat as AsyncMatch<PatternWriter<Counter<W>>>>::Future,
+                    Self::Value,
+                >;
+                fn async_match(self, matcher: TracingWriter<W>) -> Self::Future {
+                    let offset = matcher.offset();
+                    let value = debug_capped(&self);
+                    MatchTraced {
+                        future: self.async_match(matcher.inner),
+                        trace: Some(matcher.trace),
+                        pattern: stringify!($pat),
+                        offset,
+                        value: Some(value),
+                        position: write_position,
+                        wrap: wrap_writer,
+                        _phantom: PhantomData,
+                    }
+                }
+            }
+        )*
+    }
+}
+impl_traced_write!(
+    [] write::Flush, [] write::Fill, [] write::Align,
+    [] u8, [] i8,
+    [] u16, [] BE<u16>, [] LE<u16>,
+    [] i16, [] BE<i16>, [] LE<i16>,
+    [] U24, [] BE<U24>, [] LE<U24>,
+    [] I24, [] BE<I24>, [] LE<I24>,
+    [] u32, [] BE<u32>, [] LE<u32>,
+    [] i32, [] BE<i32>, [] LE<i32>,
+    [] U40, [] BE<U40>, [] LE<U40>,
+    [] I40, [] BE<I40>, [] LE<I40>,
+    [] U48, [] BE<U48>, [] LE<U48>,
+    [] I48, [] BE<I48>, [] LE<I48>,
+    [] U56, [] BE<U56>, [] LE<U56>,
+    [] I56, [] BE<I56>, [] LE<I56>,
+    [] u64, [] BE<u64>, [] LE<u64>,
+    [] i64, [] BE<i64>, [] LE<i64>,
+    [] Vec<u8>, [] String,
+    [B] Buf<B>, [B] Window<B>, [B] PartialBuf<B>
+);
+
+#[cfg(test)]
+mod test {
+    use futures::Future;
+
+    use matcher::AsyncMatch;
+    use pattern::read::{All, U8};
+    use super::*;
+
+    #[test]
+    fn short_values_are_recorded_as_is() {
+        let (reader, _) = (U8, U8).async_match(TracingReader::new(&[1, 2][..])).wait().unwrap();
+        let values = reader.trace().iter().map(|e| e.value.clone()).collect::<Vec<_>>();
+        assert_eq!(values, [Some("1".to_owned()), Some("2".to_owned())]);
+    }
+
+    #[test]
+    fn long_values_are_truncated() {
+        let input = vec![0; 1000];
+        let (reader, _) = All.async_match(TracingReader::new(&input[..])).wait().unwrap();
+        let value = reader.trace()[0].value.clone().unwrap();
+        assert_eq!(value.len(), MAX_VALUE_LEN + 3);
+        assert!(value.starts_with("[0, 0, "));
+        assert!(value.ends_with("..."));
+        assert_eq!(reader.trace()[0].size, 1000);
+
+        let writer = TracingWriter::new(Vec::new());
+        let (writer, _) = vec![0u8; 1000].async_match(writer).wait().unwrap();
+        let value = writer.trace()[0].value.clone().unwrap();
+        assert_eq!(value.len(), MAX_VALUE_LEN + 3);
+        assert_eq!(writer.into_inner().len(), 1000);
+    }
+
+    #[test]
+    fn truncation_keeps_char_boundaries() {
+        let value = debug_capped(&"\u{3042}".repeat(100));
+        assert!(value.len() <= MAX_VALUE_LEN + 3);
+        assert!(value.starts_with("\"\u{3042}"));
+        assert!(value.ends_with("\u{3042}..."));
+    }
+}
@@ -1,35 +1,215 @@
-use std::io::{Write, Result, Error};
-use futures::{Poll, Future};
+use std::cmp;
+use std::error;
+use std::io::{Write, Seek, SeekFrom, Result, Error, ErrorKind};
+use std::marker::PhantomData;
+use futures::{self, Poll, Async, Future};
 use byteorder::{ByteOrder, NativeEndian, BigEndian, LittleEndian};
 
 use pattern::{Buf, Window};
 use pattern::write::{self, U24, I24, U40, I40, U48, I48, U56, I56};
-use pattern::combinators::{PartialBuf, LE, BE};
-use matcher::{AsyncMatch, Matcher};
-use io::{AsyncWrite, AsyncIoError};
+use pattern::combinators::{PartialBuf, LE, BE, Checksummed, WithState};
+use matcher::{AsyncMatch, Matcher, TrackPosition};
+use error::AsyncError;
+use checksum::Checksum;
+use io::{AsyncWrite, AsyncIoError, FromIoError, ChecksumHook, Stateful};
+use io::{SeekResult, seek_back_error};
+use io::futures::WriteAll;
 
 /// A matcher to write patterns into the inner writer `W`.
 ///
 /// This is mainly used to define your own writing patterns.
 /// See the example of the [`WriteInto`](./trait.WriteInto.html) trait.
-pub struct PatternWriter<W>(W);
+///
+/// Like `PatternReader`, the error type `E` of this matcher defaults to `std::io::Error`
+/// and can be replaced by any error type which can be converted from `std::io::Error`.
+///
+/// By default, each primitive pattern is written to `W` directly.
+/// If the staging buffer is enabled by `PatternWriter::with_staging_buffer`,
+/// the output is coalesced in the buffer and written to `W` in large chunks.
+pub struct PatternWriter<W, E = Error> {
+    inner: W,
+    position: u64,
+    checksums: Vec<Box<dyn ChecksumHook>>,
+    staging: Vec<u8>,
+    staging_threshold: Option<usize>,
+    _error: PhantomData<E>,
+}
 impl<W: Write> PatternWriter<W> {
     /// Makes new `PatternWriter` instance.
     pub fn new(inner: W) -> Self {
-        PatternWriter(inner)
+        Self::with_error_type(inner)
+    }
+}
+impl<W: Write, E: FromIoError> PatternWriter<W, E> {
+    /// Makes new `PatternWriter` instance which has the error type `E`.
+    pub fn with_error_type(inner: W) -> Self {
+        PatternWriter {
+            inner,
+            position: 0,
+            checksums: Vec::new(),
+            staging: Vec::new(),
+            staging_threshold: None,
+            _error: PhantomData,
+        }
     }
 }
-impl<W: Write> Write for PatternWriter<W> {
+impl<W, E> PatternWriter<W, E> {
+    /// Returns the reference to the inner writer.
+    pub fn inner_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Returns the mutable reference to the inner writer.
+    pub fn inner_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Unwraps this `PatternWriter`, returning the inner writer.
+    ///
+    /// The bytes remaining in the staging buffer are discarded.
+    /// Use `PatternWriter::finish` to write them to the inner writer,
+    /// or `PatternWriter::into_parts` to take them.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+
+    /// Unwraps this `PatternWriter`, returning the inner writer and
+    /// the bytes remaining in the staging buffer.
+    ///
+    /// The returned bytes have not been written to the inner writer.
+    pub fn into_parts(self) -> (W, Vec<u8>) {
+        (self.inner, self.staging)
+    }
+
+    /// Enables the staging buffer.
+    ///
+    /// Written bytes are kept in the buffer until its length reaches `threshold`
+    /// or this writer is flushed (e.g., by the `write::Flush` pattern).
+    /// The bytes remaining in the buffer are also written by `PatternWriter::finish`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate futures;
+    /// # extern crate handy_async;
+    /// use futures::Future;
+    /// use handy_async::io::PatternWriter;
+    /// use handy_async::matcher::AsyncMatch;
+    /// use handy_async::pattern::Endian;
+    /// use handy_async::pattern::write::Flush;
+    ///
+    /// # fn main() {
+    /// let writer = PatternWriter::new(Vec::new()).with_staging_buffer(1024);
+    /// let (writer, _) = (0u8, 1u16.be(), 2u32.be()).async_match(writer).wait().unwrap();
+    /// assert_eq!(writer.staged_bytes(), [0, 0, 1, 0, 0, 0, 2]);
+    /// assert!(writer.inner_ref().is_empty());
+    ///
+    /// let (writer, _) = Flush.async_match(writer).wait().unwrap();
+    /// assert_eq!(writer.into_inner(), [0, 0, 1, 0, 0, 0, 2]);
+    ///
+    /// // The buffer is drained as soon as its length reaches the threshold.
+    /// let writer = PatternWriter::new(Vec::new()).with_staging_buffer(2);
+    /// let (writer, _) = (0u8, 1u16.be(), 2u8).async_match(writer).wait().unwrap();
+    /// assert_eq!(writer.staged_bytes(), [2]);
+    /// assert_eq!(writer.inner_ref(), &[0, 0, 1]);
+    ///
+    /// // Staged bytes are written by `finish`.
+    /// assert_eq!(writer.finish().ok().unwrap(), [0, 0, 1, 2]);
+    /// # }
+    /// ```
+    pub fn with_staging_buffer(mut self, threshold: usize) -> Self {
+        self.staging_threshold = Some(threshold);
+        self
+    }
+
+    /// Returns the bytes in the staging buffer which have not been written to the inner writer yet.
+    pub fn staged_bytes(&self) -> &[u8] {
+        &self.staging
+    }
+
+    /// Returns the total byte size written via this `PatternWriter`.
+    ///
+    /// If this writer has been seeked, this is the offset from the start of the stream.
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+
+    /// Starts feeding the bytes written from now on to a new checksum `H`.
+    fn start_checksum<H: Checksum + Send + 'static>(&mut self) {
+        self.checksums.push(Box::new(H::default()));
+    }
+
+    /// Ends the last checksum started by `start_checksum`, and returns it.
+    fn finish_checksum<H: Checksum + 'static>(&mut self) -> H {
+        let checksum = self.checksums.pop().expect("No checksum to finish");
+        *checksum.into_any().downcast().expect("Unexpected checksum type")
+    }
+}
+impl<W: Write, E> PatternWriter<W, E> {
+    /// Writes the bytes remaining in the staging buffer to the inner writer,
+    /// and returns the inner writer.
+    ///
+    /// If it fails, the error is returned with this writer (the unwritten bytes are kept).
+    pub fn finish(mut self) -> ::std::result::Result<W, AsyncIoError<Self>> {
+        match self.drain_staging() {
+            Ok(()) => Ok(self.inner),
+            Err(e) => Err(AsyncIoError::new(self, e)),
+        }
+    }
+
+    /// Writes the bytes in the staging buffer to the inner writer.
+    fn drain_staging(&mut self) -> Result<()> {
+        while !self.staging.is_empty() {
+            let size = self.inner.write(&self.staging)?;
+            if size == 0 {
+                return Err(Error::new(ErrorKind::WriteZero, "Failed to drain the staging buffer"));
+            }
+            self.staging.drain(..size);
+        }
+        Ok(())
+    }
+}
+impl<W: Write, E> Write for PatternWriter<W, E> {
     fn write(&mut self, buf: &[u8]) -> Result<usize> {
-        self.0.write(buf)
+        let size = if let Some(threshold) = self.staging_threshold {
+            if self.staging.len() >= threshold {
+                self.drain_staging()?;
+            }
+            self.staging.extend_from_slice(buf);
+            if self.staging.len() >= threshold {
+                // `buf` has been accepted, so a failure here is retried
+                // (and reported) by the next write or flush.
+                let _ = self.drain_staging();
+            }
+            buf.len()
+        } else {
+            self.inner.write(buf)?
+        };
+        for checksum in &mut self.checksums {
+            checksum.update(&buf[..size]);
+        }
+        self.position += size as u64;
+        Ok(size)
     }
     fn flush(&mut self) -> Result<()> {
-        self.0.flush()
+        self.drain_staging()?;
+        self.inner.flush()
     }
 }
-impl<W> Matcher for PatternWriter<W> {
-    type Error = Error;
+impl<W: Write + Seek, E> Seek for PatternWriter<W, E> {
+    fn seek(&mut self, pos: SeekFrom) -> Result<u64> {
+        self.drain_staging()?;
+        self.position = self.inner.seek(pos)?;
+        Ok(self.position)
+    }
+}
+impl<W, E: error::Error> Matcher for PatternWriter<W, E> {
+    type Error = E;
+    fn position(&self) -> Option<u64> {
+        Some(self.position)
+    }
 }
+impl<W, E: error::Error> TrackPosition for PatternWriter<W, E> {}
 
 /// The `WriteInto` trait allows for writing
 /// a value of this pattern to a sink asynchronously.
@@ -92,7 +272,7 @@ pub trait WriteInto<W: Write>: AsyncMatch<PatternWriter<W>> {
     /// # }
     /// ```
     fn write_into(self, writer: W) -> WritePattern<Self, W> {
-        WritePattern(self.async_match(PatternWriter(writer)))
+        WritePattern(self.async_match(PatternWriter::new(writer)))
     }
 
     /// Synchronous version of the `WriteInto::write_into` method.
@@ -123,29 +303,218 @@ where
     type Item = (W, P::Value);
     type Error = AsyncIoError<W>;
     fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
-        Ok(self.0.poll().map_err(|e| e.map_state(|w| w.0))?.map(
-            |(m, v)| {
-                (m.0, v)
-            },
+        Ok(self.0.poll().map_err(into_writer_error)?.map(
+            |(m, v)| (into_writer(m), v),
         ))
     }
 }
 
+fn into_writer_error<W>(e: AsyncIoError<PatternWriter<W>>) -> AsyncIoError<W> {
+    let e = match e.offset() {
+        None => {
+            let offset = e.state_ref().position;
+            e.with_offset(offset)
+        }
+        Some(_) => e,
+    };
+    e.map_state(into_writer)
+}
+
+fn into_writer<W>(w: PatternWriter<W>) -> W {
+    let (inner, staged) = w.into_parts();
+    // `write_into` never enables the staging buffer.
+    debug_assert!(staged.is_empty());
+    inner
+}
+
+fn into_matcher_error<W, E, B>(e: AsyncIoError<(PatternWriter<W, E>, B)>)
+                              -> AsyncError<PatternWriter<W, E>, E>
+where
+    E: From<Error>,
+{
+    e.map_state(|(w, _)| w).map_error(E::from)
+}
+
+/// A future which will match the inner pattern and compute the checksum of the written bytes.
+///
+/// This future is generally created by invoking
+/// `WriteInto::write_into` method for `Checksummed` pattern.
+pub struct WriteChecksummed<W: Write, P, H, E: FromIoError = Error>(P::Future, PhantomData<H>)
+where
+    P: AsyncMatch<PatternWriter<W, E>>;
+impl<W: Write, E: FromIoError, P, H> Future for WriteChecksummed<W, P, H, E>
+where
+    P: AsyncMatch<PatternWriter<W, E>>,
+    H: Checksum + Send + 'static,
+{
+    type Item = (PatternWriter<W, E>, (P::Value, H::Digest));
+    type Error = AsyncError<PatternWriter<W, E>, E>;
+    fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
+        match self.0.poll() {
+            Err(e) => {
+                Err(e.map_state(|mut w| {
+                    w.finish_checksum::<H>();
+                    w
+                }))
+            }
+            Ok(Async::Ready((mut w, v))) => {
+                let digest = w.finish_checksum::<H>().digest();
+                Ok(Async::Ready((w, (v, digest))))
+            }
+            Ok(Async::NotReady) => Ok(Async::NotReady),
+        }
+    }
+}
+impl<W: Write, E: FromIoError, P, H> AsyncMatch<PatternWriter<W, E>> for Checksummed<P, H>
+where
+    P: AsyncMatch<PatternWriter<W, E>>,
+    H: Checksum + Send + 'static,
+{
+    type Future = WriteChecksummed<W, P, H, E>;
+    fn async_match(self, mut matcher: PatternWriter<W, E>) -> Self::Future {
+        matcher.start_checksum::<H>();
+        WriteChecksummed(self.unwrap().async_match(matcher), PhantomData)
+    }
+}
+
+/// A future which will write a byte repeatedly.
+///
+/// This future is generally created by invoking
+/// `WriteInto::write_into` method for `Fill` or `Align` pattern.
+pub struct WriteFill<W, E = Error> {
+    future: WriteAll<PatternWriter<W, E>, Window<[u8; 256]>>,
+    remaining: usize,
+}
+impl<W: Write, E: FromIoError> WriteFill<W, E> {
+    fn new(writer: PatternWriter<W, E>, byte: u8, size: usize) -> Self {
+        let chunk = cmp::min(size, 256);
+        WriteFill {
+            future: writer.async_write_all(Window::new([byte; 256]).set_end(chunk)),
+            remaining: size - chunk,
+        }
+    }
+}
+impl<W: Write, E: FromIoError> Future for WriteFill<W, E> {
+    type Item = (PatternWriter<W, E>, ());
+    type Error = AsyncError<PatternWriter<W, E>, E>;
+    fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
+        while let Async::Ready((w, b)) = self.future.poll().map_err(into_matcher_error)? {
+            if self.remaining == 0 {
+                return Ok(Async::Ready((w, ())));
+            }
+            let chunk = cmp::min(self.remaining, 256);
+            self.remaining -= chunk;
+            self.future = w.async_write_all(b.set_end(chunk));
+        }
+        Ok(Async::NotReady)
+    }
+}
+impl<W: Write, E: FromIoError> AsyncMatch<PatternWriter<W, E>> for write::Fill {
+    type Future = WriteFill<W, E>;
+    fn async_match(self, matcher: PatternWriter<W, E>) -> Self::Future {
+        WriteFill::new(matcher, self.0, self.1)
+    }
+}
+impl<W: Write, E: FromIoError> AsyncMatch<PatternWriter<W, E>> for write::Align {
+    type Future = WriteFill<W, E>;
+    fn async_match(self, matcher: PatternWriter<W, E>) -> Self::Future {
+        let align = cmp::max(self.0, 1);
+        let padding = (align - matcher.position() % align) % align;
+        WriteFill::new(matcher, self.1, padding as usize)
+    }
+}
+
+impl<W: Write + Seek, E: FromIoError> AsyncMatch<PatternWriter<W, E>> for write::SeekTo {
+    type Future = futures::Done<(PatternWriter<W, E>, u64), AsyncError<PatternWriter<W, E>, E>>;
+    fn async_match(self, mut matcher: PatternWriter<W, E>) -> Self::Future {
+        match matcher.seek(self.0) {
+            Ok(position) => futures::done(Ok((matcher, position))),
+            Err(e) => futures::done(Err(AsyncError::new(matcher, E::from(e)))),
+        }
+    }
+}
+
+impl<W: Write, E: FromIoError> AsyncMatch<PatternWriter<W, E>> for write::Position {
+    type Future = futures::Done<(PatternWriter<W, E>, u64), AsyncError<PatternWriter<W, E>, E>>;
+    fn async_match(self, matcher: PatternWriter<W, E>) -> Self::Future {
+        let position = matcher.position();
+        futures::done(Ok((matcher, position)))
+    }
+}
+
+/// A future which will write the inner pattern at the specified offset.
+///
+/// This future is generally created by invoking
+/// `WriteInto::write_into` method for `At` pattern.
+pub struct WriteAt<W: Write, P, E: FromIoError = Error>(Option<SeekResult<P::Future, PatternWriter<W, E>, E>>)
+where
+    P: AsyncMatch<PatternWriter<W, E>>;
+impl<W: Write + Seek, E: FromIoError, P> Future for WriteAt<W, P, E>
+where
+    P: AsyncMatch<PatternWriter<W, E>>,
+{
+    type Item = (PatternWriter<W, E>, P::Value);
+    type Error = AsyncError<PatternWriter<W, E>, E>;
+    fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
+        let (mut f, restore) = self.0.take().expect("Cannot poll WriteAt twice")?;
+        match f.poll() {
+            Err(e) => {
+                let mut seek_error = None;
+                let e = e.map_state(|mut w| {
+                    seek_error = w.seek(SeekFrom::Start(restore)).err();
+                    w
+                });
+                if let Some(seek_error) = seek_error {
+                    Err(e.map_error(|e| seek_back_error(e, seek_error)))
+                } else {
+                    Err(e)
+                }
+            }
+            Ok(Async::Ready((mut w, v))) => {
+                if let Err(e) = w.seek(SeekFrom::Start(restore)) {
+                    return Err(AsyncError::new(w, E::from(e)));
+                }
+                Ok(Async::Ready((w, v)))
+            }
+            Ok(Async::NotReady) => {
+                self.0 = Some(Ok((f, restore)));
+                Ok(Async::NotReady)
+            }
+        }
+    }
+}
+impl<W: Write + Seek, E: FromIoError, P> AsyncMatch<PatternWriter<W, E>> for write::At<P>
+where
+    P: AsyncMatch<PatternWriter<W, E>>,
+{
+    type Future = WriteAt<W, P, E>;
+    fn async_match(self, mut matcher: PatternWriter<W, E>) -> Self::Future {
+        let result = matcher
+            .stream_position()
+            .and_then(|restore| matcher.seek(SeekFrom::Start(self.0)).map(|_| restore));
+        match result {
+            Ok(restore) => WriteAt(Some(Ok((self.1.async_match(matcher), restore)))),
+            Err(e) => WriteAt(Some(Err(AsyncError::new(matcher, E::from(e))))),
+        }
+    }
+}
+
+
 /// A future which will flush the internal buffer of `W`.
 ///
 /// This is created by calling `WriteInto::write_into` method for
 /// `Flush` pattern.
-pub struct WriteFlush<W>(super::futures::Flush<PatternWriter<W>>);
-impl<W: Write> Future for WriteFlush<W> {
-    type Item = (PatternWriter<W>, ());
-    type Error = AsyncIoError<PatternWriter<W>>;
+pub struct WriteFlush<W, E = Error>(super::futures::Flush<PatternWriter<W, E>>);
+impl<W: Write, E: FromIoError> Future for WriteFlush<W, E> {
+    type Item = (PatternWriter<W, E>, ());
+    type Error = AsyncError<PatternWriter<W, E>, E>;
     fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
-        Ok(self.0.poll()?.map(|m| (m, ())))
+        Ok(self.0.poll().map_err(|e| e.map_error(E::from))?.map(|m| (m, ())))
     }
 }
-impl<W: Write> AsyncMatch<PatternWriter<W>> for write::Flush {
-    type Future = WriteFlush<W>;
-    fn async_match(self, matcher: PatternWriter<W>) -> Self::Future {
+impl<W: Write, E: FromIoError> AsyncMatch<PatternWriter<W, E>> for write::Flush {
+    type Future = WriteFlush<W, E>;
+    fn async_match(self, matcher: PatternWriter<W, E>) -> Self::Future {
         WriteFlush(matcher.async_flush())
     }
 }
@@ -164,35 +533,35 @@ impl<W: Write> AsyncMatch<PatternWriter<W>> for write::Flush {
 /// Buf([0; 32]).write_into(std::io::sink());
 /// Window::new([0; 32]).skip(4).write_into(std::io::sink());
 /// ```
-pub struct WriteBuf<W, B>(super::futures::WriteAll<PatternWriter<W>, B>);
-impl<W: Write, B: AsRef<[u8]>> Future for WriteBuf<W, B> {
-    type Item = (PatternWriter<W>, B);
-    type Error = AsyncIoError<PatternWriter<W>>;
+pub struct WriteBuf<W, B, E = Error>(super::futures::WriteAll<PatternWriter<W, E>, B>);
+impl<W: Write, E: FromIoError, B: AsRef<[u8]>> Future for WriteBuf<W, B, E> {
+    type Item = (PatternWriter<W, E>, B);
+    type Error = AsyncError<PatternWriter<W, E>, E>;
     fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
-        self.0.poll().map_err(|e| e.map_state(|(w, _)| w))
+        self.0.poll().map_err(into_matcher_error)
     }
 }
-impl<W: Write, B: AsRef<[u8]>> AsyncMatch<PatternWriter<W>> for Buf<B> {
-    type Future = WriteBuf<W, B>;
-    fn async_match(self, matcher: PatternWriter<W>) -> Self::Future {
+impl<W: Write, E: FromIoError, B: AsRef<[u8]>> AsyncMatch<PatternWriter<W, E>> for Buf<B> {
+    type Future = WriteBuf<W, B, E>;
+    fn async_match(self, matcher: PatternWriter<W, E>) -> Self::Future {
         WriteBuf(matcher.async_write_all(self.0))
     }
 }
-impl<W: Write> AsyncMatch<PatternWriter<W>> for Vec<u8> {
-    type Future = WriteBuf<W, Self>;
-    fn async_match(self, matcher: PatternWriter<W>) -> Self::Future {
+impl<W: Write, E: FromIoError> AsyncMatch<PatternWriter<W, E>> for Vec<u8> {
+    type Future = WriteBuf<W, Self, E>;
+    fn async_match(self, matcher: PatternWriter<W, E>) -> Self::Future {
         WriteBuf(matcher.async_write_all(self))
     }
 }
-impl<W: Write> AsyncMatch<PatternWriter<W>> for String {
-    type Future = WriteBuf<W, Self>;
-    fn async_match(self, matcher: PatternWriter<W>) -> Self::Future {
+impl<W: Write, E: FromIoError> AsyncMatch<PatternWriter<W, E>> for String {
+    type Future = WriteBuf<W, Self, E>;
+    fn async_match(self, matcher: PatternWriter<W, E>) -> Self::Future {
         WriteBuf(matcher.async_write_all(self))
     }
 }
-impl<W: Write, B: AsRef<[u8]>> AsyncMatch<PatternWriter<W>> for Window<B> {
-    type Future = WriteBuf<W, Self>;
-    fn async_match(self, matcher: PatternWriter<W>) -> Self::Future {
+impl<W: Write, E: FromIoError, B: AsRef<[u8]>> AsyncMatch<PatternWriter<W, E>> for Window<B> {
+    type Future = WriteBuf<W, Self, E>;
+    fn async_match(self, matcher: PatternWriter<W, E>) -> Self::Future {
         WriteBuf(matcher.async_write_all(self))
     }
 }
@@ -218,37 +587,37 @@ impl<W: Write, B: AsRef<[u8]>> AsyncMatch<PatternWriter<W>> for Window<B> {
 /// assert_eq!(written_size, 4);
 /// # }
 /// ```
-pub struct WritePartialBuf<W, B>(super::futures::WriteBytes<PatternWriter<W>, B>);
-impl<W: Write, B: AsRef<[u8]>> Future for WritePartialBuf<W, B> {
-    type Item = (PatternWriter<W>, (B, usize));
-    type Error = AsyncIoError<PatternWriter<W>>;
+pub struct WritePartialBuf<W, B, E = Error>(super::futures::WriteBytes<PatternWriter<W, E>, B>);
+impl<W: Write, E: FromIoError, B: AsRef<[u8]>> Future for WritePartialBuf<W, B, E> {
+    type Item = (PatternWriter<W, E>, (B, usize));
+    type Error = AsyncError<PatternWriter<W, E>, E>;
     fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
         self.0
             .poll()
             .map(|a| a.map(|(w, b, s)| (w, (b, s))))
-            .map_err(|e| e.map_state(|(w, _)| w))
+            .map_err(into_matcher_error)
     }
 }
-impl<W: Write, B: AsRef<[u8]>> AsyncMatch<PatternWriter<W>> for PartialBuf<B> {
-    type Future = WritePartialBuf<W, B>;
-    fn async_match(self, matcher: PatternWriter<W>) -> Self::Future {
+impl<W: Write, E: FromIoError, B: AsRef<[u8]>> AsyncMatch<PatternWriter<W, E>> for PartialBuf<B> {
+    type Future = WritePartialBuf<W, B, E>;
+    fn async_match(self, matcher: PatternWriter<W, E>) -> Self::Future {
         WritePartialBuf(matcher.async_write(self.0))
     }
 }
 
 /// A future which will write a fixnum associated with `P` into `W`.
-pub struct WriteFixnum<W, P>
+pub struct WriteFixnum<W, P, E: FromIoError = Error>
 where
-    P: AsyncMatch<PatternWriter<W>>,
+    P: AsyncMatch<PatternWriter<W, E>>,
 {
     future: P::Future,
 }
-impl<W: Write, P> Future for WriteFixnum<W, P>
+impl<W: Write, E: FromIoError, P> Future for WriteFixnum<W, P, E>
 where
-    P: AsyncMatch<PatternWriter<W>>,
+    P: AsyncMatch<PatternWriter<W, E>>,
 {
-    type Item = (PatternWriter<W>, ());
-    type Error = AsyncIoError<PatternWriter<W>>;
+    type Item = (PatternWriter<W, E>, ());
+    type Error = AsyncError<PatternWriter<W, E>, E>;
     fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
         Ok(self.future.poll()?.map(|(w, _)| (w, ())))
     }
@@ -256,9 +625,9 @@ where
 
 macro_rules! impl_write_fixnum_pattern {
     ($pat:ty, $size:expr, $conv:expr) => {
-        impl<W: Write> AsyncMatch<PatternWriter<W>> for $pat {
-            type Future = WriteFixnum<W, Buf<[u8; $size]>>;
-            fn async_match(self, matcher: PatternWriter<W>) -> Self::Future {
+        impl<W: Write, E: FromIoError> AsyncMatch<PatternWriter<W, E>> for $pat {
+            type Future = WriteFixnum<W, Buf<[u8; $size]>, E>;
+            fn async_match(self, matcher: PatternWriter<W, E>) -> Self::Future {
                 let mut buf = [0; $size];
                 $conv(&mut buf[..], self);
                 let future = Buf(buf).async_match(matcher);
@@ -342,3 +711,93 @@ impl_write_fixnum_pattern!(LE<u64>, 8, |b: &mut [u8], n: Self| LittleEndian::wri
 impl_write_fixnum_pattern!(i64, 8, NativeEndian::write_i64);
 impl_write_fixnum_pattern!(BE<i64>, 8, |b: &mut [u8], n: Self| BigEndian::write_i64(b,n.0));
 impl_write_fixnum_pattern!(LE<i64>, 8, |b: &mut [u8], n: Self| LittleEndian::write_i64(b,n.0));
+
+impl<W: Write, S, E: FromIoError, F, V> AsyncMatch<PatternWriter<Stateful<W, S>, E>> for write::State<F, S>
+where
+    F: FnOnce(&mut S) -> V,
+{
+    type Future = futures::Done<(PatternWriter<Stateful<W, S>, E>, V),
+                                AsyncError<PatternWriter<Stateful<W, S>, E>, E>>;
+    fn async_match(self, mut matcher: PatternWriter<Stateful<W, S>, E>) -> Self::Future {
+        let v = (self.unwrap())(&mut matcher.inner_mut().state);
+        futures::done(Ok((matcher, v)))
+    }
+}
+
+/// A future which will match the inner pattern and
+/// apply a function to the state of the stream and the value.
+///
+/// This future is generally created by invoking
+/// `WriteInto::write_into` method for `WithState` pattern.
+pub struct WriteWithState<P, F>(Option<(P, F)>);
+impl<W, S, E, P, F, V, U> Future for WriteWithState<P, F>
+where
+    P: Future<Item = (PatternWriter<Stateful<W, S>, E>, V)>,
+    F: FnOnce(&mut S, V) -> U,
+{
+    type Item = (PatternWriter<Stateful<W, S>, E>, U);
+    type Error = P::Error;
+    fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
+        let (mut p, f) = self.0.take().expect("Cannot poll WriteWithState twice");
+        if let Async::Ready((mut matcher, v)) = p.poll()? {
+            let u = f(&mut matcher.inner_mut().state, v);
+            Ok(Async::Ready((matcher, u)))
+        } else {
+            self.0 = Some((p, f));
+            Ok(Async::NotReady)
+        }
+    }
+}
+impl<W: Write, S, E: FromIoError, P, F, U> AsyncMatch<PatternWriter<Stateful<W, S>, E>> for WithState<P, F, S>
+where
+    P: AsyncMatch<PatternWriter<Stateful<W, S>, E>>,
+    F: FnOnce(&mut S, P::Value) -> U,
+{
+    type Future = WriteWithState<P::Future, F>;
+    fn async_match(self, matcher: PatternWriter<Stateful<W, S>, E>) -> Self::Future {
+        let (p, f) = self.unwrap();
+        WriteWithState(Some((p.async_match(matcher), f)))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use futures::Future;
+
+    use io::Stateful;
+    use pattern::{Pattern, Branch};
+    use pattern::write;
+    use super::*;
+
+    #[test]
+    fn count_works_with_pattern_writer() {
+        use pattern::Endian;
+
+        let writer = PatternWriter::new(Vec::new());
+        let pattern = 0x0102u16.be().count(3);
+        let (writer, values) = pattern.async_match(writer).wait().ok().unwrap();
+        assert_eq!(values.len(), 3);
+        assert_eq!(writer.into_inner(), [1, 2, 1, 2, 1, 2]);
+    }
+
+    #[test]
+    fn state_is_shared_across_patterns() {
+        // A name is written as a reference (`1, index`) if it has been written before,
+        // otherwise as a literal (`0, len, bytes..`) and appended to the table.
+        let name = |name: &'static str| {
+            write::state(move |names: &mut Vec<String>| {
+                if let Some(i) = names.iter().position(|n| n == name) {
+                    Branch::<_, _>::A((1u8, i as u8).map(|_| ()))
+                } else {
+                    names.push(name.to_string());
+                    Branch::B((0u8, name.len() as u8, name.as_bytes().to_vec()).map(|_| ()))
+                }
+            }).and_then(|p| p)
+        };
+        let output = Stateful { stream: Vec::new(), state: Vec::new() };
+        let pattern = (name("foo"), name("bar"), name("foo"), name("bar"));
+        let (w, _) = pattern.write_into(output).wait().ok().unwrap();
+        assert_eq!(w.stream, [0, 3, b'f', b'o', b'o', 0, 3, b'b', b'a', b'r', 1, 0, 1, 1]);
+        assert_eq!(w.state, ["foo", "bar"]);
+    }
+}
@@ -69,10 +69,18 @@
 
 extern crate futures;
 extern crate byteorder;
+#[cfg(feature = "log")]
+#[macro_use]
+extern crate log;
+#[cfg(all(unix, feature = "reactor"))]
+extern crate mio;
 
 pub mod io;
 pub mod sync_io;
 pub mod pattern;
 pub mod matcher;
 pub mod error;
+#[cfg(all(unix, feature = "reactor"))]
+pub mod reactor;
 pub mod future;
+pub mod checksum;
@@ -1,10 +1,26 @@
+use std::array;
+use std::cell::Cell;
+use std::cmp;
+use std::collections::{vec_deque, HashMap, VecDeque};
+use std::convert::TryFrom;
+use std::error;
+use std::hash::Hash;
+use std::io;
+use std::iter::FromIterator;
+use std::marker::PhantomData;
+use std::ops::{Bound, RangeBounds};
 use futures::{self, Poll, Async, Future, Stream};
 
-use pattern::{Pattern, Branch, Iter};
-use pattern::combinators::{Map, AndThen, Then, OrElse, Or, Chain};
-use pattern::combinators::{IterFold, Expect, UnexpectedValue};
-use error::AsyncError;
-use super::Matcher;
+use pattern::{self, Pattern, Branch, Iter, Seq, Switch, Lazy, SwitchArm, DefaultArm};
+use pattern::combinators::{Map, MapErr, AndThen, Then, OrElse, Or, Chain};
+use pattern::combinators::{IterFold, Expect, UnexpectedValue, Label, Count};
+use pattern::combinators::{Expected, ExpectIn, ExpectOneOf, Validate};
+use pattern::combinators::{ManyUntilEos, UnknownTag, IterCollect, IterTryFold};
+use pattern::combinators::{RecursionLimitExceeded, Spanned};
+use pattern::combinators::{Checksummed, VerifyChecksum, AppendChecksum};
+use checksum::Checksum;
+use error::{AsyncError, MatchError};
+use super::{Matcher, TrackPosition};
 
 /// The `AsyncMatch` trait allows for asyncronous matching
 /// between a pattern `Self` and a matcher `M`.
@@ -107,6 +123,42 @@ where
     }
 }
 
+/// Future to do pattern matching of
+/// [`MapErr`](../../pattern/combinators/struct.MapErr.html) pattern.
+pub struct MatchMapErr<P, F>(Option<(P, F)>);
+impl<M, P, F, E, U> Future for MatchMapErr<P, F>
+where
+    P: Future<Error = AsyncError<M, E>>,
+    F: FnOnce(E) -> U,
+    E: error::Error + From<U>,
+{
+    type Item = P::Item;
+    type Error = P::Error;
+    fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
+        let (mut p, f) = self.0.take().expect("Cannot poll MatchMapErr twice");
+        match p.poll() {
+            Err(e) => Err(e.map_error(|e| From::from(f(e)))),
+            Ok(Async::NotReady) => {
+                self.0 = Some((p, f));
+                Ok(Async::NotReady)
+            }
+            Ok(ready) => Ok(ready),
+        }
+    }
+}
+impl<M: Matcher, P, F, U> AsyncMatch<M> for MapErr<P, F>
+where
+    F: FnOnce(M::Error) -> U,
+    M::Error: From<U>,
+    P: AsyncMatch<M>,
+{
+    type Future = MatchMapErr<<P as AsyncMatch<M>>::Future, F>;
+    fn async_match(self, matcher: M) -> Self::Future {
+        let (p, f) = self.unwrap();
+        MatchMapErr(Some((p.async_match(matcher), f)))
+    }
+}
+
 /// Future to do pattern matching of
 /// [`AndThen`](../../pattern/combinators/struct.AndThen.html) pattern.
 pub struct MatchAndThen<M, P0, P1, F>(Phase<(P0::Future, F), P1::Future>)
@@ -582,6 +634,200 @@ impl<M: Matcher, I, F, T> AsyncMatch<M> for IterFold<I, F, T>
     }
 }
 
+type Push<T> = fn(Vec<T>, T) -> Vec<T>;
+type MatchCollectVec<M, I, T> = MatchIterFold<M, I, Push<T>, Vec<T>>;
+fn push<T>(mut values: Vec<T>, value: T) -> Vec<T> {
+    values.push(value);
+    values
+}
+
+/// Future to do pattern matching of array patterns `[P; N]`.
+pub struct MatchArray<M: Matcher, P, const N: usize>(
+    MatchCollectVec<M, array::IntoIter<P, N>, P::Value>,
+)
+where
+    P: AsyncMatch<M>;
+impl<M: Matcher, P, const N: usize> Future for MatchArray<M, P, N>
+where
+    P: AsyncMatch<M>,
+{
+    type Item = (M, [P::Value; N]);
+    type Error = AsyncError<M, M::Error>;
+    fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
+        if let Async::Ready((m, values)) = self.0.poll()? {
+            match <[P::Value; N]>::try_from(values) {
+                Ok(values) => Ok(Async::Ready((m, values))),
+                Err(_) => unreachable!(),
+            }
+        } else {
+            Ok(Async::NotReady)
+        }
+    }
+}
+impl<M: Matcher, P, const N: usize> AsyncMatch<M> for [P; N]
+where
+    P: AsyncMatch<M>,
+{
+    type Future = MatchArray<M, P, N>;
+    fn async_match(self, matcher: M) -> Self::Future {
+        let fold = pattern::Iter(IntoIterator::into_iter(self))
+            .fold(Vec::with_capacity(N), push as Push<P::Value>);
+        MatchArray(fold.async_match(matcher))
+    }
+}
+
+/// Future to do pattern matching of
+/// [Seq](../../pattern/struct.Seq.html) pattern.
+pub struct MatchSeq<M: Matcher, P>(MatchCollectVec<M, ::std::vec::IntoIter<P>, P::Value>)
+where
+    P: AsyncMatch<M>;
+impl<M: Matcher, P> Future for MatchSeq<M, P>
+where
+    P: AsyncMatch<M>,
+{
+    type Item = (M, Vec<P::Value>);
+    type Error = AsyncError<M, M::Error>;
+    fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
+        self.0.poll()
+    }
+}
+impl<M: Matcher, P> AsyncMatch<M> for Seq<P>
+where
+    P: AsyncMatch<M>,
+{
+    type Future = MatchSeq<M, P>;
+    fn async_match(self, matcher: M) -> Self::Future {
+        let values = Vec::with_capacity(self.0.len());
+        let fold = pattern::Iter(self.0.into_iter()).fold(values, push as Push<P::Value>);
+        MatchSeq(fold.async_match(matcher))
+    }
+}
+
+/// Future to do pattern matching of `VecDeque<P>` pattern.
+pub struct MatchVecDeque<M: Matcher, P>(MatchCollectVec<M, vec_deque::IntoIter<P>, P::Value>)
+where
+    P: AsyncMatch<M>;
+impl<M: Matcher, P> Future for MatchVecDeque<M, P>
+where
+    P: AsyncMatch<M>,
+{
+    type Item = (M, Vec<P::Value>);
+    type Error = AsyncError<M, M::Error>;
+    fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
+        self.0.poll()
+    }
+}
+impl<M: Matcher, P> AsyncMatch<M> for VecDeque<P>
+where
+    P: AsyncMatch<M>,
+{
+    type Future = MatchVecDeque<M, P>;
+    fn async_match(self, matcher: M) -> Self::Future {
+        let values = Vec::with_capacity(self.len());
+        let fold = pattern::Iter(self.into_iter()).fold(values, push as Push<P::Value>);
+        MatchVecDeque(fold.async_match(matcher))
+    }
+}
+
+/// Future to do pattern matching of
+/// [`IterCollect`](../../pattern/combinators/struct.IterCollect.html) pattern.
+pub struct MatchIterCollect<M: Matcher, I, C>(MatchCollectVec<M, I, <I::Item as Pattern>::Value>,
+                                              PhantomData<C>)
+where
+    I: Iterator,
+    I::Item: AsyncMatch<M>;
+impl<M: Matcher, I, C> Future for MatchIterCollect<M, I, C>
+where
+    I: Iterator,
+    I::Item: AsyncMatch<M>,
+    C: FromIterator<<I::Item as Pattern>::Value>,
+{
+    type Item = (M, C);
+    type Error = AsyncError<M, M::Error>;
+    fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
+        if let Async::Ready((m, values)) = self.0.poll()? {
+            Ok(Async::Ready((m, values.into_iter().collect())))
+        } else {
+            Ok(Async::NotReady)
+        }
+    }
+}
+impl<M: Matcher, I, C> AsyncMatch<M> for IterCollect<I, C>
+where
+    I: Iterator,
+    I::Item: AsyncMatch<M>,
+    C: FromIterator<<I::Item as Pattern>::Value>,
+{
+    type Future = MatchIterCollect<M, I, C>;
+    fn async_match(self, matcher: M) -> Self::Future {
+        let iter = self.unwrap();
+        let values = Vec::with_capacity(iter.size_hint().0);
+        let fold = pattern::Iter(iter).fold(values, push as Push<_>);
+        MatchIterCollect(fold.async_match(matcher), PhantomData)
+    }
+}
+
+/// Future to do pattern matching of
+/// [`IterTryFold`](../../pattern/combinators/struct.IterTryFold.html) pattern.
+#[cfg_attr(feature = "cargo-clippy", allow(type_complexity))]
+pub struct MatchIterTryFold<M: Matcher, I, F, T>
+where
+    I: Iterator,
+    I::Item: AsyncMatch<M>,
+{
+    phase: Phase<(<I::Item as AsyncMatch<M>>::Future, I, T, F), (M, T)>,
+}
+impl<M: Matcher, I, F, T, E> Future for MatchIterTryFold<M, I, F, T>
+where
+    I: Iterator,
+    I::Item: AsyncMatch<M>,
+    F: Fn(T, <I::Item as Pattern>::Value) -> Result<T, E>,
+    M::Error: From<E>,
+{
+    type Item = (M, T);
+    type Error = AsyncError<M, M::Error>;
+    fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
+        match self.phase.take() {
+            Phase::A((mut f, mut iter, acc, fold)) => {
+                if let Async::Ready((m, v)) = f.poll()? {
+                    let acc = match fold(acc, v) {
+                        Ok(acc) => acc,
+                        Err(e) => return Err(AsyncError::new(m, From::from(e))),
+                    };
+                    if let Some(p) = iter.next() {
+                        self.phase = Phase::A((p.async_match(m), iter, acc, fold));
+                        self.poll()
+                    } else {
+                        Ok(Async::Ready((m, acc)))
+                    }
+                } else {
+                    self.phase = Phase::A((f, iter, acc, fold));
+                    Ok(Async::NotReady)
+                }
+            }
+            Phase::B((m, v)) => Ok(Async::Ready((m, v))),
+            _ => panic!("Cannot poll MatchIterTryFold twice"),
+        }
+    }
+}
+impl<M: Matcher, I, F, T, E> AsyncMatch<M> for IterTryFold<I, F, T>
+where
+    I: Iterator,
+    I::Item: AsyncMatch<M>,
+    F: Fn(T, <I::Item as Pattern>::Value) -> Result<T, E>,
+    M::Error: From<E>,
+{
+    type Future = MatchIterTryFold<M, I, F, T>;
+    fn async_match(self, matcher: M) -> Self::Future {
+        let (mut iter, fold, acc) = self.unwrap();
+        if let Some(p) = iter.next() {
+            MatchIterTryFold { phase: Phase::A((p.async_match(matcher), iter, acc, fold)) }
+        } else {
+            MatchIterTryFold { phase: Phase::B((matcher, acc)) }
+        }
+    }
+}
+
 /// Future to do pattern matching of
 /// [Iter](../../pattern/struct.Iter.html) pattern.
 pub struct MatchIter<M: Matcher, I>(Phase<(<I::Item as AsyncMatch<M>>::Future, I), M>)
@@ -633,7 +879,7 @@ where
 
 /// Future to do pattern matching of
 /// [Expect](../../pattern/struct.Expect.html) pattern.
-pub struct MatchExpect<M: Matcher, P>(P::Future, P::Value)
+pub struct MatchExpect<M: Matcher, P>(P::Future, Option<P::Value>)
 where
     P: AsyncMatch<M>;
 impl<M: Matcher, P> Future for MatchExpect<M, P>
@@ -646,10 +892,12 @@ where
     type Error = AsyncError<M, M::Error>;
     fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
         if let Async::Ready((m, v)) = self.0.poll()? {
-            if v == self.1 {
+            let expected = self.1.take().expect("Cannot poll MatchExpect twice");
+            if v == expected {
                 Ok(Async::Ready((m, v)))
             } else {
-                let e = From::from(UnexpectedValue(v));
+                let expected = Expected::Eq(expected);
+                let e = From::from(UnexpectedValue::new(v, expected));
                 Err(AsyncError::new(m, e))
             }
         } else {
@@ -666,7 +914,591 @@ where
     type Future = MatchExpect<M, P>;
     fn async_match(self, matcher: M) -> Self::Future {
         let (pattern, expected_value) = self.unwrap();
-        MatchExpect(pattern.async_match(matcher), expected_value)
+        MatchExpect(pattern.async_match(matcher), Some(expected_value))
+    }
+}
+
+/// Future to do pattern matching of
+/// [ExpectIn](../../pattern/combinators/struct.ExpectIn.html) pattern.
+pub struct MatchExpectIn<M: Matcher, P>(P::Future, Option<Bounds<P::Value>>)
+where
+    P: AsyncMatch<M>;
+type Bounds<T> = (Bound<T>, Bound<T>);
+impl<M: Matcher, P> Future for MatchExpectIn<M, P>
+where
+    P: AsyncMatch<M>,
+    P::Value: PartialOrd,
+    M::Error: From<UnexpectedValue<P::Value>>,
+{
+    type Item = (M, P::Value);
+    type Error = AsyncError<M, M::Error>;
+    fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
+        if let Async::Ready((m, v)) = self.0.poll()? {
+            let (start, end) = self.1.take().expect("Cannot poll MatchExpectIn twice");
+            if (start.as_ref(), end.as_ref()).contains(&v) {
+                Ok(Async::Ready((m, v)))
+            } else {
+                let expected = Expected::Range(start, end);
+                let e = From::from(UnexpectedValue::new(v, expected));
+                Err(AsyncError::new(m, e))
+            }
+        } else {
+            Ok(Async::NotReady)
+        }
+    }
+}
+impl<M: Matcher, P> AsyncMatch<M> for ExpectIn<P>
+where
+    P: AsyncMatch<M>,
+    P::Value: PartialOrd,
+    M::Error: From<UnexpectedValue<P::Value>>,
+{
+    type Future = MatchExpectIn<M, P>;
+    fn async_match(self, matcher: M) -> Self::Future {
+        let (pattern, start, end) = self.unwrap();
+        MatchExpectIn(pattern.async_match(matcher), Some((start, end)))
+    }
+}
+
+/// Future to do pattern matching of
+/// [ExpectOneOf](../../pattern/combinators/struct.ExpectOneOf.html) pattern.
+pub struct MatchExpectOneOf<M: Matcher, P>(P::Future, Option<Vec<P::Value>>)
+where
+    P: AsyncMatch<M>;
+impl<M: Matcher, P> Future for MatchExpectOneOf<M, P>
+where
+    P: AsyncMatch<M>,
+    P::Value: PartialEq,
+    M::Error: From<UnexpectedValue<P::Value>>,
+{
+    type Item = (M, P::Value);
+    type Error = AsyncError<M, M::Error>;
+    fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
+        if let Async::Ready((m, v)) = self.0.poll()? {
+            let candidates = self.1.take().expect("Cannot poll MatchExpectOneOf twice");
+            if candidates.contains(&v) {
+                Ok(Async::Ready((m, v)))
+            } else {
+                let expected = Expected::OneOf(candidates);
+                let e = From::from(UnexpectedValue::new(v, expected));
+                Err(AsyncError::new(m, e))
+            }
+        } else {
+            Ok(Async::NotReady)
+        }
+    }
+}
+impl<M: Matcher, P> AsyncMatch<M> for ExpectOneOf<P>
+where
+    P: AsyncMatch<M>,
+    P::Value: PartialEq,
+    M::Error: From<UnexpectedValue<P::Value>>,
+{
+    type Future = MatchExpectOneOf<M, P>;
+    fn async_match(self, matcher: M) -> Self::Future {
+        let (pattern, candidates) = self.unwrap();
+        MatchExpectOneOf(pattern.async_match(matcher), Some(candidates))
+    }
+}
+
+/// Future to do pattern matching of
+/// [Validate](../../pattern/combinators/struct.Validate.html) pattern.
+pub struct MatchValidate<M: Matcher, P, F>(P::Future, F, &'static str)
+where
+    P: AsyncMatch<M>;
+impl<M: Matcher, P, F> Future for MatchValidate<M, P, F>
+where
+    P: AsyncMatch<M>,
+    F: Fn(&P::Value) -> bool,
+    M::Error: From<UnexpectedValue<P::Value>>,
+{
+    type Item = (M, P::Value);
+    type Error = AsyncError<M, M::Error>;
+    fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
+        if let Async::Ready((m, v)) = self.0.poll()? {
+            if (self.1)(&v) {
+                Ok(Async::Ready((m, v)))
+            } else {
+                let expected = Expected::Description(self.2);
+                let e = From::from(UnexpectedValue::new(v, expected));
+                Err(AsyncError::new(m, e))
+            }
+        } else {
+            Ok(Async::NotReady)
+        }
+    }
+}
+impl<M: Matcher, P, F> AsyncMatch<M> for Validate<P, F>
+where
+    P: AsyncMatch<M>,
+    F: Fn(&P::Value) -> bool,
+    M::Error: From<UnexpectedValue<P::Value>>,
+{
+    type Future = MatchValidate<M, P, F>;
+    fn async_match(self, matcher: M) -> Self::Future {
+        let (pattern, pred, message) = self.unwrap();
+        MatchValidate(pattern.async_match(matcher), pred, message)
+    }
+}
+
+/// Future to do pattern matching of
+/// [Label](../../pattern/combinators/struct.Label.html) pattern.
+pub struct MatchLabel<M: Matcher, P>(P::Future, &'static str)
+where
+    P: AsyncMatch<M>;
+impl<M: Matcher, P> Future for MatchLabel<M, P>
+where
+    P: AsyncMatch<M>,
+{
+    type Item = (M, P::Value);
+    type Error = AsyncError<M, M::Error>;
+    fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
+        self.0.poll().map_err(|e| {
+            let e = match (e.offset(), e.state_ref().position()) {
+                (None, Some(offset)) => e.with_offset(offset),
+                _ => e,
+            };
+            e.with_label(self.1)
+        })
+    }
+}
+impl<M: Matcher, P> AsyncMatch<M> for Label<P>
+where
+    P: AsyncMatch<M>,
+{
+    type Future = MatchLabel<M, P>;
+    fn async_match(self, matcher: M) -> Self::Future {
+        let (pattern, label) = self.unwrap();
+        MatchLabel(pattern.async_match(matcher), label)
+    }
+}
+
+/// Future to do pattern matching of
+/// [Count](../../pattern/combinators/struct.Count.html) pattern.
+pub struct MatchCount<M: Matcher, P, C>
+where
+    P: AsyncMatch<M>,
+{
+    phase: Phase<P::Future, M>,
+    pattern: P,
+    values: Vec<P::Value>,
+    n: usize,
+    _collection: PhantomData<C>,
+}
+impl<M: Matcher, P, C> Future for MatchCount<M, P, C>
+where
+    P: AsyncMatch<M> + Clone,
+    C: FromIterator<P::Value>,
+{
+    type Item = (M, C);
+    type Error = AsyncError<M, M::Error>;
+    fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
+        loop {
+            match self.phase.take() {
+                Phase::A(mut f) => {
+                    if let Async::Ready((m, v)) = f.poll()? {
+                        self.values.push(v);
+                        if self.values.len() < self.n {
+                            self.phase = Phase::A(self.pattern.clone().async_match(m));
+                        } else {
+                            self.phase = Phase::B(m);
+                        }
+                    } else {
+                        self.phase = Phase::A(f);
+                        return Ok(Async::NotReady);
+                    }
+                }
+                Phase::B(m) => {
+                    let values = ::std::mem::take(&mut self.values);
+                    return Ok(Async::Ready((m, values.into_iter().collect())));
+                }
+                Phase::Polled => panic!("Cannot poll MatchCount twice"),
+            }
+        }
+    }
+}
+impl<M: Matcher, P, C> AsyncMatch<M> for Count<P, C>
+where
+    P: AsyncMatch<M> + Clone,
+    C: FromIterator<P::Value>,
+{
+    type Future = MatchCount<M, P, C>;
+    fn async_match(self, matcher: M) -> Self::Future {
+        let (pattern, n) = self.unwrap();
+        let phase = if n == 0 {
+            Phase::B(matcher)
+        } else {
+            Phase::A(pattern.clone().async_match(matcher))
+        };
+        MatchCount {
+            phase,
+            pattern,
+            values: Vec::new(),
+            n,
+            _collection: PhantomData,
+        }
+    }
+}
+
+/// Future to do pattern matching of
+/// [ManyUntilEos](../../pattern/combinators/struct.ManyUntilEos.html) pattern.
+pub struct MatchManyUntilEos<M, P, C>
+where
+    M: TrackPosition,
+    P: AsyncMatch<M>,
+{
+    future: Option<P::Future>,
+    pattern: P,
+    start: Option<u64>,
+    values: Vec<P::Value>,
+    _collection: PhantomData<C>,
+}
+impl<M, P, C> Future for MatchManyUntilEos<M, P, C>
+where
+    M: TrackPosition,
+    M::Error: MatchError + From<io::Error>,
+    P: AsyncMatch<M> + Clone,
+    C: FromIterator<P::Value>,
+{
+    type Item = (M, C);
+    type Error = AsyncError<M, M::Error>;
+    fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
+        loop {
+            let mut f = self.future.take().expect("Cannot poll MatchManyUntilEos twice");
+            match f.poll() {
+                Err(e) => {
+                    let is_eos = e.error_ref().is_eos() && e.state_ref().position() == self.start;
+                    if !is_eos {
+                        return Err(e);
+                    }
+                    let (m, _) = e.unwrap();
+                    let values = ::std::mem::take(&mut self.values);
+                    return Ok(Async::Ready((m, values.into_iter().collect())));
+                }
+                Ok(Async::Ready((m, v))) => {
+                    let position = m.position();
+                    if position == self.start {
+                        let e = io::Error::new(io::ErrorKind::InvalidInput,
+                                               "The repeated pattern consumed no bytes");
+                        return Err(AsyncError::new(m, e.into()));
+                    }
+                    self.values.push(v);
+                    self.start = position;
+                    self.future = Some(self.pattern.clone().async_match(m));
+                }
+                Ok(Async::NotReady) => {
+                    self.future = Some(f);
+                    return Ok(Async::NotReady);
+                }
+            }
+        }
+    }
+}
+impl<M, P, C> AsyncMatch<M> for ManyUntilEos<P, C>
+where
+    M: TrackPosition,
+    M::Error: MatchError + From<io::Error>,
+    P: AsyncMatch<M> + Clone,
+    C: FromIterator<P::Value>,
+{
+    type Future = MatchManyUntilEos<M, P, C>;
+    fn async_match(self, matcher: M) -> Self::Future {
+        let pattern = self.unwrap();
+        let start = matcher.position();
+        MatchManyUntilEos {
+            future: Some(pattern.clone().async_match(matcher)),
+            pattern,
+            start,
+            values: Vec::new(),
+            _collection: PhantomData,
+        }
+    }
+}
+
+/// Future to do pattern matching of
+/// [Switch](../../pattern/struct.Switch.html) pattern.
+pub struct MatchSwitch<M: Matcher, P, A>
+where
+    P: AsyncMatch<M>,
+    A: AsyncMatch<M>,
+{
+    phase: Phase<P::Future, A::Future>,
+    arms: HashMap<P::Value, SwitchArm<A>>,
+    default: Option<DefaultArm<P::Value, A>>,
+}
+impl<M: Matcher, P, A> Future for MatchSwitch<M, P, A>
+where
+    P: AsyncMatch<M>,
+    P::Value: Eq + Hash,
+    A: AsyncMatch<M>,
+    M::Error: From<UnknownTag<P::Value>>,
+{
+    type Item = (M, A::Value);
+    type Error = AsyncError<M, M::Error>;
+    fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
+        match self.phase.take() {
+            Phase::A(mut f) => {
+                if let Async::Ready((m, tag)) = f.poll()? {
+                    let arm = match self.arms.get(&tag) {
+                        Some(arm) => arm(),
+                        None => {
+                            if let Some(ref default) = self.default {
+                                default(tag)
+                            } else {
+                                return Err(AsyncError::new(m, From::from(UnknownTag(tag))));
+                            }
+                        }
+                    };
+                    self.phase = Phase::B(arm.async_match(m));
+                    self.poll()
+                } else {
+                    self.phase = Phase::A(f);
+                    Ok(Async::NotReady)
+                }
+            }
+            Phase::B(mut f) => {
+                let result = f.poll()?;
+                if let Async::NotReady = result {
+                    self.phase = Phase::B(f);
+                }
+                Ok(result)
+            }
+            Phase::Polled => panic!("Cannot poll MatchSwitch twice"),
+        }
+    }
+}
+impl<M: Matcher, P, A> AsyncMatch<M> for Switch<P, A>
+where
+    P: AsyncMatch<M>,
+    P::Value: Eq + Hash,
+    A: AsyncMatch<M>,
+    M::Error: From<UnknownTag<P::Value>>,
+{
+    type Future = MatchSwitch<M, P, A>;
+    fn async_match(self, matcher: M) -> Self::Future {
+        let (tag, arms, default) = self.unwrap();
+        MatchSwitch {
+            phase: Phase::A(tag.async_match(matcher)),
+            arms,
+            default,
+        }
+    }
+}
+
+/// Future to do pattern matching of
+/// [Spanned](../../pattern/combinators/struct.Spanned.html) pattern.
+pub struct MatchSpanned<M: TrackPosition, P: AsyncMatch<M>>(P::Future, u64);
+impl<M: TrackPosition, P: AsyncMatch<M>> Future for MatchSpanned<M, P> {
+    type Item = (M, (P::Value, u64, u64));
+    type Error = AsyncError<M, M::Error>;
+    fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
+        if let Async::Ready((m, v)) = self.0.poll()? {
+            let end = m.position().expect("Position is not tracked");
+            Ok(Async::Ready((m, (v, self.1, end))))
+        } else {
+            Ok(Async::NotReady)
+        }
+    }
+}
+impl<M: TrackPosition, P: AsyncMatch<M>> AsyncMatch<M> for Spanned<P> {
+    type Future = MatchSpanned<M, P>;
+    fn async_match(self, matcher: M) -> Self::Future {
+        let start = matcher.position().expect("Position is not tracked");
+        MatchSpanned(self.unwrap().async_match(matcher), start)
+    }
+}
+
+type ChecksummedFuture<M, P, H> = <Checksummed<P, H> as AsyncMatch<M>>::Future;
+type VerifyPhase<M, P, H, Q> = Phase<
+    (ChecksummedFuture<M, P, H>, Q),
+    (<P as Pattern>::Value, <H as Checksum>::Digest, <Q as AsyncMatch<M>>::Future),
+>;
+type AppendPhase<M, P, H, F, Q> = Phase<
+    (ChecksummedFuture<M, P, H>, F),
+    (<P as Pattern>::Value, <Q as AsyncMatch<M>>::Future),
+>;
+
+/// Future to do pattern matching of
+/// [VerifyChecksum](../../pattern/combinators/struct.VerifyChecksum.html) pattern.
+pub struct MatchVerifyChecksum<M: Matcher, P: Pattern, H: Checksum, Q>
+where
+    Checksummed<P, H>: AsyncMatch<M>,
+    Q: AsyncMatch<M>,
+{
+    phase: VerifyPhase<M, P, H, Q>,
+}
+impl<M: Matcher, P: Pattern, H: Checksum, Q> Future for MatchVerifyChecksum<M, P, H, Q>
+where
+    Checksummed<P, H>: AsyncMatch<M, Value = (P::Value, H::Digest)>,
+    Q: AsyncMatch<M, Value = H::Digest>,
+    H::Digest: PartialEq,
+    M::Error: From<UnexpectedValue<H::Digest>>,
+{
+    type Item = (M, P::Value);
+    type Error = AsyncError<M, M::Error>;
+    fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
+        match self.phase.take() {
+            Phase::A((mut f, field)) => {
+                if let Async::Ready((m, (v, digest))) = f.poll()? {
+                    self.phase = Phase::B((v, digest, field.async_match(m)));
+                    self.poll()
+                } else {
+                    self.phase = Phase::A((f, field));
+                    Ok(Async::NotReady)
+                }
+            }
+            Phase::B((v, digest, mut f)) => {
+                if let Async::Ready((m, actual)) = f.poll()? {
+                    if actual == digest {
+                        Ok(Async::Ready((m, v)))
+                    } else {
+                        let e = UnexpectedValue::new(actual, Expected::Eq(digest));
+                        Err(AsyncError::new(m, From::from(e)))
+                    }
+                } else {
+                    self.phase = Phase::B((v, digest, f));
+                    Ok(Async::NotReady)
+                }
+            }
+            Phase::Polled => panic!("Cannot poll MatchVerifyChecksum twice"),
+        }
+    }
+}
+impl<M: Matcher, P: Pattern, H: Checksum, Q> AsyncMatch<M> for VerifyChecksum<P, H, Q>
+where
+    Checksummed<P, H>: AsyncMatch<M, Value = (P::Value, H::Digest)>,
+    Q: AsyncMatch<M, Value = H::Digest>,
+    H::Digest: PartialEq,
+    M::Error: From<UnexpectedValue<H::Digest>>,
+{
+    type Future = MatchVerifyChecksum<M, P, H, Q>;
+    fn async_match(self, matcher: M) -> Self::Future {
+        let (p, field) = self.unwrap();
+        MatchVerifyChecksum { phase: Phase::A((p.async_match(matcher), field)) }
+    }
+}
+
+/// Future to do pattern matching of
+/// [AppendChecksum](../../pattern/combinators/struct.AppendChecksum.html) pattern.
+pub struct MatchAppendChecksum<M: Matcher, P: Pattern, H: Checksum, F, Q>
+where
+    Checksummed<P, H>: AsyncMatch<M>,
+    Q: AsyncMatch<M>,
+{
+    phase: AppendPhase<M, P, H, F, Q>,
+}
+impl<M: Matcher, P: Pattern, H: Checksum, F, Q> Future for MatchAppendChecksum<M, P, H, F, Q>
+where
+    Checksummed<P, H>: AsyncMatch<M, Value = (P::Value, H::Digest)>,
+    F: FnOnce(H::Digest) -> Q,
+    Q: AsyncMatch<M>,
+{
+    type Item = (M, P::Value);
+    type Error = AsyncError<M, M::Error>;
+    fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
+        match self.phase.take() {
+            Phase::A((mut f, field)) => {
+                if let Async::Ready((m, (v, digest))) = f.poll()? {
+                    self.phase = Phase::B((v, field(digest).async_match(m)));
+                    self.poll()
+                } else {
+                    self.phase = Phase::A((f, field));
+                    Ok(Async::NotReady)
+                }
+            }
+            Phase::B((v, mut f)) => {
+                if let Async::Ready((m, _)) = f.poll()? {
+                    Ok(Async::Ready((m, v)))
+                } else {
+                    self.phase = Phase::B((v, f));
+                    Ok(Async::NotReady)
+                }
+            }
+            Phase::Polled => panic!("Cannot poll MatchAppendChecksum twice"),
+        }
+    }
+}
+impl<M: Matcher, P: Pattern, H: Checksum, F, Q> AsyncMatch<M> for AppendChecksum<P, H, F>
+where
+    Checksummed<P, H>: AsyncMatch<M, Value = (P::Value, H::Digest)>,
+    F: FnOnce(H::Digest) -> Q,
+    Q: AsyncMatch<M>,
+{
+    type Future = MatchAppendChecksum<M, P, H, F, Q>;
+    fn async_match(self, matcher: M) -> Self::Future {
+        let (p, field) = self.unwrap();
+        MatchAppendChecksum { phase: Phase::A((p.async_match(matcher), field)) }
+    }
+}
+
+thread_local! {
+    // The remaining nesting depth allowed for the `Lazy` patterns being polled.
+    static LAZY_DEPTH: Cell<usize> = const { Cell::new(usize::MAX) };
+}
+
+// Restores the remaining depth on drop.
+struct DepthGuard(usize);
+impl DepthGuard {
+    fn enter(remaining: usize) -> Self {
+        DepthGuard(LAZY_DEPTH.with(|d| d.replace(remaining)))
+    }
+}
+impl Drop for DepthGuard {
+    fn drop(&mut self) {
+        LAZY_DEPTH.with(|d| d.set(self.0));
+    }
+}
+
+/// Future to do pattern matching of
+/// [Lazy](../../pattern/struct.Lazy.html) pattern.
+pub struct MatchLazy<M: Matcher, F, P: AsyncMatch<M>> {
+    phase: Phase<(F, M), P::Future>,
+    max_depth: usize,
+}
+impl<M: Matcher, F, P> Future for MatchLazy<M, F, P>
+where
+    F: FnOnce() -> P,
+    P: AsyncMatch<M>,
+    M::Error: From<RecursionLimitExceeded>,
+{
+    type Item = (M, P::Value);
+    type Error = AsyncError<M, M::Error>;
+    fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
+        match self.phase.take() {
+            Phase::A((f, m)) => {
+                let available = cmp::min(self.max_depth, LAZY_DEPTH.with(|d| d.get()));
+                if available == 0 {
+                    return Err(AsyncError::new(m, From::from(RecursionLimitExceeded)));
+                }
+                self.max_depth = available;
+                self.phase = Phase::B(f().async_match(m));
+                self.poll()
+            }
+            Phase::B(mut p) => {
+                let _guard = DepthGuard::enter(self.max_depth - 1);
+                let result = p.poll()?;
+                if let Async::NotReady = result {
+                    self.phase = Phase::B(p);
+                }
+                Ok(result)
+            }
+            Phase::Polled => panic!("Cannot poll MatchLazy twice"),
+        }
+    }
+}
+impl<M: Matcher, F, P> AsyncMatch<M> for Lazy<F>
+where
+    F: FnOnce() -> P,
+    P: AsyncMatch<M>,
+    M::Error: From<RecursionLimitExceeded>,
+{
+    type Future = MatchLazy<M, F, P>;
+    fn async_match(self, matcher: M) -> Self::Future {
+        let (f, max_depth) = self.unwrap();
+        MatchLazy {
+            phase: Phase::A((f, matcher)),
+            max_depth,
+        }
     }
 }
 
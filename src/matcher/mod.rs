@@ -5,11 +5,19 @@ pub use self::async_match::AsyncMatch;
 
 pub mod futures {
     //! Futures used to match commonly used patterns.
-    pub use super::async_match::{MatchMap, MatchAndThen, MatchThen, MatchChain};
+    pub use super::async_match::{MatchMap, MatchMapErr, MatchAndThen, MatchThen, MatchChain};
     pub use super::async_match::{MatchOrElse, MatchOr, MatchOption};
-    pub use super::async_match::{MatchIter, MatchIterFold, MatchExpect};
+    pub use super::async_match::{MatchIter, MatchIterFold, MatchExpect, MatchLabel};
+    pub use super::async_match::{MatchCount, MatchManyUntilEos, MatchSwitch};
+    pub use super::async_match::{MatchLazy, MatchSpanned};
+    pub use super::async_match::{MatchVerifyChecksum, MatchAppendChecksum};
+    pub use super::async_match::{MatchArray, MatchSeq, MatchVecDeque};
+    pub use super::async_match::{MatchIterCollect, MatchIterTryFold};
+    pub use super::async_match::{MatchExpectIn, MatchExpectOneOf, MatchValidate};
     pub use super::match_tuple::{MatchTuple3, MatchTuple4, MatchTuple5, MatchTuple6};
     pub use super::match_tuple::{MatchTuple7, MatchTuple8, MatchTuple9, MatchTuple10};
+    pub use super::match_tuple::{MatchTuple11, MatchTuple12, MatchTuple13, MatchTuple14};
+    pub use super::match_tuple::{MatchTuple15, MatchTuple16};
 }
 
 pub mod streams {
@@ -75,4 +83,16 @@ mod match_tuple;
 pub trait Matcher {
     /// The error type that may occur when matching using this matcher.
     type Error: error::Error;
+
+    /// Returns the absolute byte position of this matcher, if it is tracked.
+    ///
+    /// This is used to report the offset at which an error occurred.
+    /// The default implementation returns `None`.
+    fn position(&self) -> Option<u64> {
+        None
+    }
 }
+
+/// A marker trait for matchers which always track their position
+/// (i.e., `Matcher::position` never returns `None`).
+pub trait TrackPosition: Matcher {}
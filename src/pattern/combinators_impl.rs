@@ -1,7 +1,11 @@
+use std::fmt;
 use std::io;
+use std::iter::FromIterator;
 use std::marker::PhantomData;
+use std::ops::{Bound, RangeBounds};
 use futures::{self, Poll, Async};
 
+use checksum::Checksum;
 use super::{Pattern, Endian};
 
 /// A pattern for the `then` combinator,
@@ -121,6 +125,24 @@ pub fn map<P, F>(pattern: P, map: F) -> Map<P, F> {
     Map(pattern, map)
 }
 
+/// A pattern for the `map_err` combinator, mapping an error of a pattern to another error.
+///
+/// This pattern is created by calling `Pattern::map_err` method.
+#[derive(Debug)]
+pub struct MapErr<P, F>(P, F);
+impl<P, F> MapErr<P, F> {
+    #[allow(missing_docs)]
+    pub fn unwrap(self) -> (P, F) {
+        (self.0, self.1)
+    }
+}
+impl<P: Pattern, F> Pattern for MapErr<P, F> {
+    type Value = P::Value;
+}
+pub fn map_err<P, F>(pattern: P, map_err: F) -> MapErr<P, F> {
+    MapErr(pattern, map_err)
+}
+
 /// A pattern for the `chain` combinator,
 /// chaining values of the two pattern `P0` and `P1` as a tuple value.
 ///
@@ -176,6 +198,62 @@ pub fn iter_fold<I, F, T>(iter: I, fold: F, init: T) -> IterFold<I, F, T> {
     IterFold(iter, fold, init)
 }
 
+/// A pattern for the `collect` combinator,
+/// collecting values of the patterns contained in a iterator into a collection.
+///
+/// This pattern is created by calling `Iter::collect` method.
+#[derive(Debug)]
+pub struct IterCollect<I, C>(I, PhantomData<C>);
+impl<I, C> IterCollect<I, C> {
+    #[allow(missing_docs)]
+    pub fn unwrap(self) -> I {
+        self.0
+    }
+    #[allow(missing_docs)]
+    pub fn iter_ref(&self) -> &I {
+        &self.0
+    }
+}
+impl<I, P, C> Pattern for IterCollect<I, C>
+where
+    I: Iterator<Item = P>,
+    P: Pattern,
+    C: FromIterator<P::Value>,
+{
+    type Value = C;
+}
+pub fn iter_collect<I, C>(iter: I) -> IterCollect<I, C> {
+    IterCollect(iter, PhantomData)
+}
+
+/// A pattern for the `try_fold` combinator,
+/// folding values of the patterns contained in a iterator with a fallible function.
+///
+/// This pattern is created by calling `Iter::try_fold` method.
+#[derive(Debug)]
+pub struct IterTryFold<I, F, T>(I, F, T);
+impl<I, F, T> IterTryFold<I, F, T> {
+    #[allow(missing_docs)]
+    pub fn unwrap(self) -> (I, F, T) {
+        (self.0, self.1, self.2)
+    }
+    #[allow(missing_docs)]
+    pub fn iter_ref(&self) -> &I {
+        &self.0
+    }
+}
+impl<I, P, F, T, E> Pattern for IterTryFold<I, F, T>
+where
+    I: Iterator<Item = P>,
+    P: Pattern,
+    F: Fn(T, P::Value) -> Result<T, E>,
+{
+    type Value = T;
+}
+pub fn iter_try_fold<I, F, T>(iter: I, fold: F, init: T) -> IterTryFold<I, F, T> {
+    IterTryFold(iter, fold, init)
+}
+
 /// A pattern to indicates that "T is a little endian value".
 ///
 /// This pattern is created by calling `Endian::le` method.
@@ -241,6 +319,14 @@ pub fn repeat<P>(pattern: P) -> Repeat<P> {
 ///
 /// This pattern is created by calling `Pattern::expect_eq` method.
 pub struct Expect<P: Pattern>(P, P::Value);
+impl<P: Pattern + Clone> Clone for Expect<P>
+where
+    P::Value: Clone,
+{
+    fn clone(&self) -> Self {
+        Expect(self.0.clone(), self.1.clone())
+    }
+}
 impl<P: Pattern> Expect<P> {
     #[allow(missing_docs)]
     pub fn unwrap(self) -> (P, P::Value) {
@@ -257,6 +343,544 @@ where
     Expect(pattern, expected_value)
 }
 
+/// A pattern for the `expect_in` combinator,
+/// validating that the value of a pattern is contained in a range.
+///
+/// This pattern is created by calling `Pattern::expect_in` method.
+pub struct ExpectIn<P: Pattern>(P, Bound<P::Value>, Bound<P::Value>);
+impl<P: Pattern + Clone> Clone for ExpectIn<P>
+where
+    P::Value: Clone,
+{
+    fn clone(&self) -> Self {
+        ExpectIn(self.0.clone(), self.1.clone(), self.2.clone())
+    }
+}
+impl<P: Pattern + fmt::Debug> fmt::Debug for ExpectIn<P>
+where
+    P::Value: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("ExpectIn").field(&self.0).field(&self.1).field(&self.2).finish()
+    }
+}
+impl<P: Pattern> ExpectIn<P> {
+    #[allow(missing_docs)]
+    pub fn unwrap(self) -> (P, Bound<P::Value>, Bound<P::Value>) {
+        (self.0, self.1, self.2)
+    }
+}
+impl<P: Pattern> Pattern for ExpectIn<P> {
+    type Value = P::Value;
+}
+pub fn expect_in<P: Pattern, R>(pattern: P, range: R) -> ExpectIn<P>
+where
+    P::Value: PartialOrd + Clone,
+    R: RangeBounds<P::Value>,
+{
+    ExpectIn(pattern, range.start_bound().cloned(), range.end_bound().cloned())
+}
+
+/// A pattern for the `expect_one_of` combinator,
+/// validating that the value of a pattern is one of the candidates.
+///
+/// This pattern is created by calling `Pattern::expect_one_of` method.
+pub struct ExpectOneOf<P: Pattern>(P, Vec<P::Value>);
+impl<P: Pattern + Clone> Clone for ExpectOneOf<P>
+where
+    P::Value: Clone,
+{
+    fn clone(&self) -> Self {
+        ExpectOneOf(self.0.clone(), self.1.clone())
+    }
+}
+impl<P: Pattern + fmt::Debug> fmt::Debug for ExpectOneOf<P>
+where
+    P::Value: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("ExpectOneOf").field(&self.0).field(&self.1).finish()
+    }
+}
+impl<P: Pattern> ExpectOneOf<P> {
+    #[allow(missing_docs)]
+    pub fn unwrap(self) -> (P, Vec<P::Value>) {
+        (self.0, self.1)
+    }
+}
+impl<P: Pattern> Pattern for ExpectOneOf<P> {
+    type Value = P::Value;
+}
+pub fn expect_one_of<P: Pattern>(pattern: P, candidates: &[P::Value]) -> ExpectOneOf<P>
+where
+    P::Value: PartialEq + Clone,
+{
+    ExpectOneOf(pattern, candidates.to_vec())
+}
+
+/// A pattern for the `validate` combinator,
+/// validating the value of a pattern by a predicate.
+///
+/// This pattern is created by calling `Pattern::validate` method.
+#[derive(Debug, Clone)]
+pub struct Validate<P, F>(P, F, &'static str);
+impl<P, F> Validate<P, F> {
+    #[allow(missing_docs)]
+    pub fn unwrap(self) -> (P, F, &'static str) {
+        (self.0, self.1, self.2)
+    }
+}
+impl<P: Pattern, F> Pattern for Validate<P, F>
+where
+    F: Fn(&P::Value) -> bool,
+{
+    type Value = P::Value;
+}
+pub fn validate<P: Pattern, F>(pattern: P, pred: F, message: &'static str) -> Validate<P, F>
+where
+    F: Fn(&P::Value) -> bool,
+{
+    Validate(pattern, pred, message)
+}
+
 /// An unexpected value.
+///
+/// The first field is the actual value, and the second one describes the expected value.
+#[derive(Debug)]
+pub struct UnexpectedValue<T>(pub T, pub Expected<T>);
+impl<T> UnexpectedValue<T> {
+    /// Makes new `UnexpectedValue` instance.
+    pub fn new(actual: T, expected: Expected<T>) -> Self {
+        UnexpectedValue(actual, expected)
+    }
+
+    /// Returns the description of the expected value.
+    pub fn expected(&self) -> &Expected<T> {
+        &self.1
+    }
+}
+
+/// The description of an expected value.
+#[derive(Debug)]
+pub enum Expected<T> {
+    /// The value equal to this.
+    Eq(T),
+
+    /// A value contained in this range.
+    Range(Bound<T>, Bound<T>),
+
+    /// One of these values.
+    OneOf(Vec<T>),
+
+    /// A value satisfying the described condition.
+    Description(&'static str),
+}
+impl<T: fmt::Debug> fmt::Display for Expected<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Expected::Eq(ref v) => write!(f, "{:?}", v),
+            Expected::Range(ref start, ref end) => {
+                match *start {
+                    Bound::Included(ref v) => write!(f, "in [{:?}, ", v)?,
+                    Bound::Excluded(ref v) => write!(f, "in ({:?}, ", v)?,
+                    Bound::Unbounded => write!(f, "in (-inf, ")?,
+                }
+                match *end {
+                    Bound::Included(ref v) => write!(f, "{:?}]", v),
+                    Bound::Excluded(ref v) => write!(f, "{:?})", v),
+                    Bound::Unbounded => write!(f, "inf)"),
+                }
+            }
+            Expected::OneOf(ref vs) => write!(f, "one of {:?}", vs),
+            Expected::Description(d) => write!(f, "{}", d),
+        }
+    }
+}
+
+/// A tag which has no corresponding arm in a `Switch` pattern.
+#[derive(Debug)]
+pub struct UnknownTag<T>(pub T);
+
+/// An error which indicates that the nesting of `Lazy` patterns exceeded the depth limit.
+#[derive(Debug)]
+pub struct RecursionLimitExceeded;
+
+/// A pattern for the `label` combinator, attaching a label to the errors of a pattern.
+///
+/// This pattern is created by calling `Pattern::label` method.
+#[derive(Debug, Clone)]
+pub struct Label<P>(P, &'static str);
+impl<P> Label<P> {
+    #[allow(missing_docs)]
+    pub fn unwrap(self) -> (P, &'static str) {
+        (self.0, self.1)
+    }
+}
+impl<P: Pattern> Pattern for Label<P> {
+    type Value = P::Value;
+}
+pub fn label<P>(pattern: P, label: &'static str) -> Label<P> {
+    Label(pattern, label)
+}
+
+/// A pattern for the `spanned` combinator,
+/// attaching the start and end offsets to the value of a pattern.
+///
+/// This pattern is created by calling `Pattern::spanned` method.
+#[derive(Debug, Clone)]
+pub struct Spanned<P>(P);
+impl<P> Spanned<P> {
+    #[allow(missing_docs)]
+    pub fn unwrap(self) -> P {
+        self.0
+    }
+}
+impl<P: Pattern> Pattern for Spanned<P> {
+    type Value = (P::Value, u64, u64);
+}
+pub fn spanned<P>(pattern: P) -> Spanned<P> {
+    Spanned(pattern)
+}
+
+/// A pattern for the `with_state` combinator,
+/// applying a function to the state of the matcher and the value of a pattern.
+///
+/// This pattern is created by calling `Pattern::with_state` method.
+#[derive(Debug)]
+pub struct WithState<P, F, T>(P, F, PhantomData<T>);
+impl<P, F, T> WithState<P, F, T> {
+    #[allow(missing_docs)]
+    pub fn unwrap(self) -> (P, F) {
+        (self.0, self.1)
+    }
+}
+impl<P, F, T, U> Pattern for WithState<P, F, T>
+where
+    P: Pattern,
+    F: FnOnce(&mut T, P::Value) -> U,
+{
+    type Value = U;
+}
+pub fn with_state<P, F, T>(pattern: P, f: F) -> WithState<P, F, T> {
+    WithState(pattern, f, PhantomData)
+}
+
+/// A pattern for the `checksummed` combinator,
+/// computing the checksum `H` of the bytes consumed (or produced) by a pattern.
+///
+/// This pattern is created by calling `Pattern::checksummed` method.
+#[derive(Debug)]
+pub struct Checksummed<P, H>(P, PhantomData<H>);
+impl<P, H> Checksummed<P, H> {
+    /// Makes a pattern which reads the checksum field by `field` after this pattern,
+    /// and verifies that it equals to the computed checksum.
+    ///
+    /// If the checksums differ, the matching fails with the `UnexpectedValue` error.
+    pub fn verify<Q>(self, field: Q) -> VerifyChecksum<P, H, Q> {
+        VerifyChecksum(self, field)
+    }
+
+    /// Makes a pattern which appends the checksum field made by `f` after this pattern.
+    pub fn append<F>(self, f: F) -> AppendChecksum<P, H, F> {
+        AppendChecksum(self, f)
+    }
+
+    #[allow(missing_docs)]
+    pub fn unwrap(self) -> P {
+        self.0
+    }
+}
+impl<P: Clone, H> Clone for Checksummed<P, H> {
+    fn clone(&self) -> Self {
+        Checksummed(self.0.clone(), PhantomData)
+    }
+}
+impl<P: Pattern, H: Checksum> Pattern for Checksummed<P, H> {
+    type Value = (P::Value, H::Digest);
+}
+pub fn checksummed<P, H>(pattern: P) -> Checksummed<P, H> {
+    Checksummed(pattern, PhantomData)
+}
+
+/// A pattern which verifies the trailing checksum field of a `Checksummed` pattern.
+///
+/// This pattern is created by calling `Checksummed::verify` method.
+#[derive(Debug)]
+pub struct VerifyChecksum<P, H, Q>(Checksummed<P, H>, Q);
+impl<P, H, Q> VerifyChecksum<P, H, Q> {
+    #[allow(missing_docs)]
+    pub fn unwrap(self) -> (Checksummed<P, H>, Q) {
+        (self.0, self.1)
+    }
+}
+impl<P: Pattern, H: Checksum, Q> Pattern for VerifyChecksum<P, H, Q> {
+    type Value = P::Value;
+}
+
+/// A pattern which appends the checksum field to a `Checksummed` pattern.
+///
+/// This pattern is created by calling `Checksummed::append` method.
 #[derive(Debug)]
-pub struct UnexpectedValue<T>(pub T);
+pub struct AppendChecksum<P, H, F>(Checksummed<P, H>, F);
+impl<P, H, F> AppendChecksum<P, H, F> {
+    #[allow(missing_docs)]
+    pub fn unwrap(self) -> (Checksummed<P, H>, F) {
+        (self.0, self.1)
+    }
+}
+impl<P: Pattern, H: Checksum, F> Pattern for AppendChecksum<P, H, F> {
+    type Value = P::Value;
+}
+
+/// A pattern for the `many` combinator,
+/// repeating a pattern and collecting the resulting values.
+///
+/// This pattern is created by calling `Pattern::many` method.
+#[derive(Debug, Clone)]
+pub struct Many<P: Pattern, C = Vec<<P as Pattern>::Value>> {
+    pattern: P,
+    min: usize,
+    max: usize,
+    _collection: PhantomData<C>,
+}
+impl<P: Pattern, C> Many<P, C> {
+    /// Changes the type of the collection in which the values will be collected.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::BTreeSet;
+    /// use handy_async::io::ReadFrom;
+    /// use handy_async::pattern::Pattern;
+    /// use handy_async::pattern::read::U8;
+    ///
+    /// let pattern = U8.expect_in(1..4).many(0, 8).collect::<BTreeSet<_>>();
+    /// let set = pattern.sync_read_from(&[3, 1, 3, 2, 0][..]).unwrap();
+    /// assert_eq!(set.into_iter().collect::<Vec<_>>(), [1, 2, 3]);
+    /// ```
+    pub fn collect<D>(self) -> Many<P, D>
+    where
+        D: FromIterator<P::Value>,
+    {
+        Many {
+            pattern: self.pattern,
+            min: self.min,
+            max: self.max,
+            _collection: PhantomData,
+        }
+    }
+
+    #[allow(missing_docs)]
+    pub fn unwrap(self) -> (P, usize, usize) {
+        (self.pattern, self.min, self.max)
+    }
+}
+impl<P: Pattern, C> Pattern for Many<P, C>
+where
+    C: FromIterator<P::Value>,
+{
+    type Value = C;
+}
+pub fn many<P: Pattern>(pattern: P, min: usize, max: usize) -> Many<P> {
+    Many {
+        pattern,
+        min,
+        max: ::std::cmp::max(min, max),
+        _collection: PhantomData,
+    }
+}
+
+/// A pattern for the `count` combinator,
+/// repeating a pattern exactly the given number of times and collecting the resulting values.
+///
+/// This pattern is created by calling `Pattern::count` method.
+#[derive(Debug, Clone)]
+pub struct Count<P: Pattern, C = Vec<<P as Pattern>::Value>> {
+    pattern: P,
+    n: usize,
+    _collection: PhantomData<C>,
+}
+impl<P: Pattern, C> Count<P, C> {
+    /// Changes the type of the collection in which the values will be collected.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::BTreeSet;
+    /// use handy_async::io::ReadFrom;
+    /// use handy_async::pattern::Pattern;
+    /// use handy_async::pattern::read::U8;
+    ///
+    /// let pattern = U8.count(4).collect::<BTreeSet<_>>();
+    /// let set = pattern.sync_read_from(&[3, 1, 3, 2][..]).unwrap();
+    /// assert_eq!(set.into_iter().collect::<Vec<_>>(), [1, 2, 3]);
+    /// ```
+    pub fn collect<D>(self) -> Count<P, D>
+    where
+        D: FromIterator<P::Value>,
+    {
+        Count {
+            pattern: self.pattern,
+            n: self.n,
+            _collection: PhantomData,
+        }
+    }
+
+    #[allow(missing_docs)]
+    pub fn unwrap(self) -> (P, usize) {
+        (self.pattern, self.n)
+    }
+}
+impl<P: Pattern, C> Pattern for Count<P, C>
+where
+    C: FromIterator<P::Value>,
+{
+    type Value = C;
+}
+pub fn count<P: Pattern>(pattern: P, n: usize) -> Count<P> {
+    Count {
+        pattern,
+        n,
+        _collection: PhantomData,
+    }
+}
+
+/// A pattern for the `many_until_eos` combinator,
+/// repeating a pattern until the "End-Of-Stream" is reached.
+///
+/// This pattern is created by calling `Pattern::many_until_eos` method.
+#[derive(Debug, Clone)]
+pub struct ManyUntilEos<P: Pattern, C = Vec<<P as Pattern>::Value>>(P, PhantomData<C>);
+impl<P: Pattern, C> ManyUntilEos<P, C> {
+    /// Changes the type of the collection in which the values will be collected.
+    pub fn collect<D>(self) -> ManyUntilEos<P, D>
+    where
+        D: FromIterator<P::Value>,
+    {
+        ManyUntilEos(self.0, PhantomData)
+    }
+
+    #[allow(missing_docs)]
+    pub fn unwrap(self) -> P {
+        self.0
+    }
+}
+impl<P: Pattern, C> Pattern for ManyUntilEos<P, C>
+where
+    C: FromIterator<P::Value>,
+{
+    type Value = C;
+}
+pub fn many_until_eos<P: Pattern>(pattern: P) -> ManyUntilEos<P> {
+    ManyUntilEos(pattern, PhantomData)
+}
+
+/// A pattern for the `sep_by` combinator,
+/// collecting the values of a pattern separated by another pattern.
+///
+/// This pattern is created by calling `Pattern::sep_by` method.
+#[derive(Debug, Clone)]
+pub struct SepBy<P: Pattern, S, C = Vec<<P as Pattern>::Value>> {
+    pattern: P,
+    sep: S,
+    max_items: usize,
+    _collection: PhantomData<C>,
+}
+impl<P: Pattern, S, C> SepBy<P, S, C> {
+    /// Sets the maximum number of items.
+    ///
+    /// If more items than the limit are found, the matching will fail.
+    ///
+    /// The default value is `usize::MAX`.
+    pub fn max_items(mut self, max_items: usize) -> Self {
+        self.max_items = max_items;
+        self
+    }
+
+    /// Changes the type of the collection in which the values will be collected.
+    pub fn collect<D>(self) -> SepBy<P, S, D>
+    where
+        D: FromIterator<P::Value>,
+    {
+        SepBy {
+            pattern: self.pattern,
+            sep: self.sep,
+            max_items: self.max_items,
+            _collection: PhantomData,
+        }
+    }
+
+    #[allow(missing_docs)]
+    pub fn unwrap(self) -> (P, S, usize) {
+        (self.pattern, self.sep, self.max_items)
+    }
+}
+impl<P: Pattern, S, C> Pattern for SepBy<P, S, C>
+where
+    C: FromIterator<P::Value>,
+{
+    type Value = C;
+}
+pub fn sep_by<P: Pattern, S>(pattern: P, sep: S) -> SepBy<P, S> {
+    SepBy {
+        pattern,
+        sep,
+        max_items: usize::MAX,
+        _collection: PhantomData,
+    }
+}
+
+/// A pattern for the `terminated_by` combinator,
+/// collecting the values of a pattern until a terminator pattern is matched.
+///
+/// This pattern is created by calling `Pattern::terminated_by` method.
+#[derive(Debug, Clone)]
+pub struct TerminatedBy<P: Pattern, T, C = Vec<<P as Pattern>::Value>> {
+    pattern: P,
+    end: T,
+    max_items: usize,
+    _collection: PhantomData<C>,
+}
+impl<P: Pattern, T, C> TerminatedBy<P, T, C> {
+    /// Sets the maximum number of items.
+    ///
+    /// If the terminator is not found after the limit is reached, the matching will fail.
+    ///
+    /// The default value is `usize::MAX`.
+    pub fn max_items(mut self, max_items: usize) -> Self {
+        self.max_items = max_items;
+        self
+    }
+
+    /// Changes the type of the collection in which the values will be collected.
+    pub fn collect<D>(self) -> TerminatedBy<P, T, D>
+    where
+        D: FromIterator<P::Value>,
+    {
+        TerminatedBy {
+            pattern: self.pattern,
+            end: self.end,
+            max_items: self.max_items,
+            _collection: PhantomData,
+        }
+    }
+
+    #[allow(missing_docs)]
+    pub fn unwrap(self) -> (P, T, usize) {
+        (self.pattern, self.end, self.max_items)
+    }
+}
+impl<P: Pattern, T, C> Pattern for TerminatedBy<P, T, C>
+where
+    C: FromIterator<P::Value>,
+{
+    type Value = C;
+}
+pub fn terminated_by<P: Pattern, T>(pattern: P, end: T) -> TerminatedBy<P, T> {
+    TerminatedBy {
+        pattern,
+        end,
+        max_items: usize::MAX,
+        _collection: PhantomData,
+    }
+}
@@ -1,12 +1,19 @@
 //! Patterns.
+use std::collections::{HashMap, VecDeque};
+use std::hash::Hash;
+use std::iter::FromIterator;
 use std::mem;
+use std::ops::RangeBounds;
+use std::sync::Arc;
 use std::usize;
 use futures::{self, Future};
 
+use checksum::Checksum;
 use matcher::{AsyncMatch, Matcher};
 use error::AsyncError;
 
 type BoxFuture<T, E> = Box<Future<Item = T, Error = E> + Send + 'static>;
+type LocalBoxFuture<T, E> = Box<dyn Future<Item = T, Error = E> + 'static>;
 
 pub mod read;
 pub mod write;
@@ -17,15 +24,22 @@ pub mod combinators {
     pub use super::combinators_impl::AndThen;
     pub use super::combinators_impl::OrElse;
     pub use super::combinators_impl::Or;
-    pub use super::combinators_impl::Map;
+    pub use super::combinators_impl::{Map, MapErr};
     pub use super::combinators_impl::Chain;
     pub use super::combinators_impl::IterFold;
+    pub use super::combinators_impl::{IterCollect, IterTryFold};
     pub use super::combinators_impl::BE;
     pub use super::combinators_impl::LE;
     pub use super::combinators_impl::PartialBuf;
     pub use super::combinators_impl::Repeat;
     pub use super::combinators_impl::Expect;
-    pub use super::combinators_impl::UnexpectedValue;
+    pub use super::combinators_impl::{UnexpectedValue, Expected};
+    pub use super::combinators_impl::{ExpectIn, ExpectOneOf, Validate};
+    pub use super::combinators_impl::{UnknownTag, RecursionLimitExceeded};
+    pub use super::combinators_impl::{Label, Spanned, WithState};
+    pub use super::combinators_impl::{Checksummed, VerifyChecksum, AppendChecksum};
+    pub use super::combinators_impl::{Many, Count, ManyUntilEos};
+    pub use super::combinators_impl::{SepBy, TerminatedBy};
 }
 mod combinators_impl;
 
@@ -78,6 +92,42 @@ pub trait Pattern: Sized {
         combinators_impl::map(self, f)
     }
 
+    /// Takes a closure which maps an error to another error, and
+    /// creates a pattern which calls that closure if the evaluation of `self` failed.
+    ///
+    /// The closure can return any error type `U` which is convertible into
+    /// the error type of the matcher (i.e., `M::Error: From<U>`).
+    /// This is typically used with a matcher whose error type is a user defined one
+    /// (e.g., `PatternReader::with_error_type`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate futures;
+    /// # extern crate handy_async;
+    /// use std::io;
+    /// use futures::Future;
+    /// use handy_async::pattern::Pattern;
+    /// use handy_async::pattern::read::U8;
+    /// use handy_async::io::ReadFrom;
+    ///
+    /// # fn main() {
+    /// let pattern = U8.expect_eq(0).map_err(|e| {
+    ///     io::Error::new(io::ErrorKind::InvalidData, format!("header: {}", e))
+    /// });
+    /// let error = pattern.read_from(&[1][..]).wait().err().unwrap().into_error();
+    /// assert_eq!(error.to_string(), "header: Unexpected value: 1 (expected 0)");
+    ///
+    /// // `io::ErrorKind` is converted into `io::Error`
+    /// let pattern = U8.expect_eq(0).map_err(|_| io::ErrorKind::InvalidInput);
+    /// let error = pattern.read_from(&[1][..]).wait().err().unwrap().into_error();
+    /// assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+    /// # }
+    /// ```
+    fn map_err<F>(self, f: F) -> combinators::MapErr<Self, F> {
+        combinators_impl::map_err(self, f)
+    }
+
     /// Takes two patterns and creates a new pattern over both in sequence.
     ///
     /// In generally, using the tuple pattern `(self, P)` is more convenient way to
@@ -97,6 +147,177 @@ pub trait Pattern: Sized {
         combinators_impl::repeat(self)
     }
 
+    /// Creates a pattern which repeats this pattern at least `min` and at most `max` times,
+    /// and collects the resulting values into a `Vec`.
+    ///
+    /// The repetition stops when `max` values have been collected or
+    /// the matching of this pattern does not match
+    /// (see [`MatchError::is_mismatch`](../error/trait.MatchError.html#tymethod.is_mismatch)).
+    /// In the latter case, the error is discarded if `min` values have been collected,
+    /// otherwise it is returned as the result.
+    /// The other errors (e.g., I/O errors) are always returned as the result.
+    /// Bytes consumed by a mismatched attempt beyond the `min` values are pushed back,
+    /// so they can be read by the following patterns.
+    /// Because of this lookahead, this pattern is available only for `PatternReader`.
+    /// Use `Pattern::count` for other matchers.
+    ///
+    /// If `max` is less than `min`, it is regarded as `min`.
+    ///
+    /// The collection type can be changed via the `Many::collect` method.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use handy_async::io::ReadFrom;
+    /// use handy_async::pattern::Pattern;
+    /// use handy_async::pattern::read::U8;
+    ///
+    /// let pattern = U8.expect_eq(1).many(0, 3);
+    /// assert_eq!(pattern.sync_read_from(&[1, 1, 1, 1][..]).unwrap(), [1, 1, 1]);
+    ///
+    /// let pattern = U8.expect_eq(1).many(2, 3);
+    /// assert_eq!(pattern.sync_read_from(&[1, 1, 0][..]).unwrap(), [1, 1]);
+    ///
+    /// let pattern = U8.expect_eq(1).many(2, 3);
+    /// assert!(pattern.sync_read_from(&[1, 0][..]).is_err());
+    /// ```
+    fn many(self, min: usize, max: usize) -> combinators::Many<Self>
+    where
+        Self: Clone,
+    {
+        combinators_impl::many(self, min, max)
+    }
+
+    /// Creates a pattern which repeats this pattern exactly `n` times,
+    /// and collects the resulting values into a `Vec`.
+    ///
+    /// This is equivalent to `self.many(n, n)`, but no lookahead is required,
+    /// so this pattern is available for any matcher (e.g., `PatternWriter`).
+    ///
+    /// The collection type can be changed via the `Count::collect` method.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use handy_async::io::{ReadFrom, WriteInto};
+    /// use handy_async::pattern::{Pattern, Endian};
+    /// use handy_async::pattern::read::{U8, U16};
+    ///
+    /// // The number of items is determined by the preceding field.
+    /// let pattern = U8.and_then(|n| U16.be().count(n as usize));
+    /// let items = pattern.sync_read_from(&[2, 0, 1, 0, 2, 0, 3][..]).unwrap();
+    /// assert_eq!(items, [1, 2]);
+    ///
+    /// let mut output = Vec::new();
+    /// 7u8.count(3).sync_write_into(&mut output).unwrap();
+    /// assert_eq!(output, [7, 7, 7]);
+    /// ```
+    fn count(self, n: usize) -> combinators::Count<Self>
+    where
+        Self: Clone,
+    {
+        combinators_impl::count(self, n)
+    }
+
+    /// Creates a pattern which repeats this pattern until the "End-Of-Stream" is reached,
+    /// and collects the resulting values into a `Vec`.
+    ///
+    /// The "End-Of-Stream" is detected when a matching of this pattern fails with
+    /// an `UnexpectedEof` error (see `MatchError::is_eos`) without consuming any bytes.
+    /// If the failed matching consumed some bytes, the error will be returned as the result.
+    /// So this pattern is available only for matchers which track their position
+    /// (see `TrackPosition`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use handy_async::io::ReadFrom;
+    /// use handy_async::pattern::{Pattern, Endian};
+    /// use handy_async::pattern::read::U16;
+    ///
+    /// let pattern = U16.be().many_until_eos();
+    /// assert_eq!(pattern.sync_read_from(&[0, 1, 0, 2][..]).unwrap(), [1, 2]);
+    ///
+    /// let pattern = U16.be().many_until_eos();
+    /// assert!(pattern.sync_read_from(&[0, 1, 0][..]).is_err());
+    /// ```
+    fn many_until_eos(self) -> combinators::ManyUntilEos<Self>
+    where
+        Self: Clone,
+    {
+        combinators_impl::many_until_eos(self)
+    }
+
+    /// Creates a pattern which collects the values of this pattern separated by `sep`.
+    ///
+    /// Zero or more items are matched.
+    /// The repetition stops when the first item or a separator does not match
+    /// (see `MatchError::is_mismatch`);
+    /// the bytes consumed by that failed attempt are pushed back to the reader.
+    /// The other errors (e.g., I/O errors) are returned as the result.
+    /// An item following a matched separator is mandatory.
+    ///
+    /// Because this pattern requires lookahead,
+    /// it is only available for `PatternReader` (`TracingReader` does not support it).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use handy_async::io::ReadFrom;
+    /// use handy_async::pattern::Pattern;
+    /// use handy_async::pattern::read::U8;
+    ///
+    /// let pattern = (U8.sep_by(U8.expect_eq(b',')), U8);
+    /// let (items, rest) = pattern.sync_read_from(&b"a,b,c;"[..]).unwrap();
+    /// assert_eq!(items, b"abc");
+    /// assert_eq!(rest, b';');
+    ///
+    /// // Too many items
+    /// let pattern = U8.sep_by(U8.expect_eq(b',')).max_items(2);
+    /// assert!(pattern.sync_read_from(&b"a,b,c;"[..]).is_err());
+    /// ```
+    fn sep_by<S>(self, sep: S) -> combinators::SepBy<Self, S>
+    where
+        Self: Clone,
+        S: Pattern + Clone,
+    {
+        combinators_impl::sep_by(self, sep)
+    }
+
+    /// Creates a pattern which collects the values of this pattern until `end` is matched.
+    ///
+    /// Before each item, `end` is tried;
+    /// if it matches, the bytes consumed by it are discarded and the repetition ends.
+    /// If it does not match (see `MatchError::is_mismatch`),
+    /// the bytes are pushed back to the reader and the next item is matched.
+    /// The other errors (e.g., I/O errors) are returned as the result.
+    ///
+    /// Because this pattern requires lookahead,
+    /// it is only available for `PatternReader` (`TracingReader` does not support it).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use handy_async::io::ReadFrom;
+    /// use handy_async::pattern::{Pattern, Endian};
+    /// use handy_async::pattern::read::{U8, U16};
+    ///
+    /// // A list of (tag, value) entries terminated by a zero tag.
+    /// let entry = (U8, U16.be());
+    /// let pattern = (entry.terminated_by(U8.expect_eq(0)), U8);
+    /// let input = [1, 0, 10, 2, 0, 20, 0, 99];
+    /// let (entries, rest) = pattern.sync_read_from(&input[..]).unwrap();
+    /// assert_eq!(entries, [(1, 10), (2, 20)]);
+    /// assert_eq!(rest, 99);
+    /// ```
+    fn terminated_by<T>(self, end: T) -> combinators::TerminatedBy<Self, T>
+    where
+        Self: Clone,
+        T: Pattern + Clone,
+    {
+        combinators_impl::terminated_by(self, end)
+    }
+
     /// Takes an expected value and creates a pattern which
     /// performs a pattern matching and validates that
     /// the matched value is equal to the expected one.
@@ -118,6 +339,196 @@ pub trait Pattern: Sized {
         combinators_impl::expect(self, expected_value)
     }
 
+    /// Creates a pattern which validates that
+    /// the matched value is contained in `range`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use handy_async::pattern::Pattern;
+    /// use handy_async::pattern::read::U8;
+    /// use handy_async::io::ReadFrom;
+    ///
+    /// assert!(U8.expect_in(b'A'..=b'Z').sync_read_from(&b"Hello"[..]).is_ok());
+    ///
+    /// let e = U8.expect_in(b'a'..=b'z').sync_read_from(&b"Hello"[..]).err().unwrap();
+    /// assert_eq!(e.kind(), std::io::ErrorKind::InvalidData);
+    /// assert_eq!(e.to_string(), "Unexpected value: 72 (expected in [97, 122])");
+    ///
+    /// let e = U8.expect_in(..b'A').sync_read_from(&b"Hello"[..]).err().unwrap();
+    /// assert_eq!(e.to_string(), "Unexpected value: 72 (expected in (-inf, 65))");
+    /// ```
+    fn expect_in<R>(self, range: R) -> combinators::ExpectIn<Self>
+    where
+        Self::Value: PartialOrd + Clone,
+        R: RangeBounds<Self::Value>,
+    {
+        combinators_impl::expect_in(self, range)
+    }
+
+    /// Creates a pattern which validates that
+    /// the matched value is one of `candidates`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use handy_async::pattern::Pattern;
+    /// use handy_async::pattern::read::U8;
+    /// use handy_async::io::ReadFrom;
+    ///
+    /// assert!(U8.expect_one_of(&[1, 2, 4]).sync_read_from(&[4][..]).is_ok());
+    ///
+    /// let e = U8.expect_one_of(&[1, 2, 4]).sync_read_from(&[3][..]).err().unwrap();
+    /// assert_eq!(e.to_string(), "Unexpected value: 3 (expected one of [1, 2, 4])");
+    /// ```
+    fn expect_one_of(self, candidates: &[Self::Value]) -> combinators::ExpectOneOf<Self>
+    where
+        Self::Value: PartialEq + Clone,
+    {
+        combinators_impl::expect_one_of(self, candidates)
+    }
+
+    /// Creates a pattern which validates the matched value by `pred`.
+    ///
+    /// If `pred` returns `false`, the matching fails with
+    /// an `UnexpectedValue` error described by `message`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use handy_async::pattern::Pattern;
+    /// use handy_async::pattern::read::U8;
+    /// use handy_async::io::ReadFrom;
+    ///
+    /// let even = || U8.validate(|n| n % 2 == 0, "an even number");
+    /// assert!(even().sync_read_from(&[2][..]).is_ok());
+    ///
+    /// let e = even().sync_read_from(&[3][..]).err().unwrap();
+    /// assert_eq!(e.to_string(), "Unexpected value: 3 (expected an even number)");
+    /// ```
+    fn validate<F>(self, pred: F, message: &'static str) -> combinators::Validate<Self, F>
+    where
+        F: Fn(&Self::Value) -> bool,
+    {
+        combinators_impl::validate(self, pred, message)
+    }
+
+    /// Attaches `label` to the errors which occur while matching this pattern.
+    ///
+    /// Labels of nested patterns are joined into a path
+    /// (e.g., `"header.flags"`), which can be retrieved via
+    /// [`AsyncError::path`](../error/struct.AsyncError.html#method.path).
+    /// In addition, the byte offset at which the error was detected will be recorded
+    /// if the matcher tracks its position.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate futures;
+    /// # extern crate handy_async;
+    /// use futures::Future;
+    /// use handy_async::pattern::Pattern;
+    /// use handy_async::pattern::read::U8;
+    /// use handy_async::io::ReadFrom;
+    ///
+    /// # fn main() {
+    /// let pattern = (U8, U8.expect_eq(0).label("flags")).label("header");
+    /// let e = pattern.read_from(&[1, 2][..]).wait().err().unwrap();
+    /// assert_eq!(e.path(), ["header", "flags"]);
+    /// assert_eq!(e.offset(), Some(2));
+    /// # }
+    /// ```
+    fn label(self, label: &'static str) -> combinators::Label<Self> {
+        combinators_impl::label(self, label)
+    }
+
+    /// Creates a pattern which yields the value of `self` together with
+    /// the start and end offsets of the matcher.
+    ///
+    /// This pattern is available only for matchers which track their position
+    /// (see `TrackPosition`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use handy_async::io::ReadFrom;
+    /// use handy_async::pattern::{Pattern, Endian};
+    /// use handy_async::pattern::read::{U8, U16};
+    ///
+    /// let pattern = (U8, U16.be().spanned());
+    /// assert_eq!(pattern.sync_read_from(&[0, 1, 2][..]).unwrap(), (0, (0x0102, 1, 3)));
+    /// ```
+    fn spanned(self) -> combinators::Spanned<Self> {
+        combinators_impl::spanned(self)
+    }
+
+    /// Takes a closure which is applied to the state of the matcher and the value of `self`.
+    ///
+    /// This can be used with the matchers over `Stateful` streams
+    /// (i.e., `PatternReader<Stateful<S, T>>` and `PatternWriter<Stateful<S, T>>`),
+    /// so that context-sensitive formats can be handled declaratively.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use handy_async::io::{ReadFrom, Stateful};
+    /// use handy_async::pattern::Pattern;
+    /// use handy_async::pattern::read::U8;
+    ///
+    /// // The second byte is a delta from the first one.
+    /// let input = Stateful { stream: &[10, 3][..], state: 0 };
+    /// let pattern = (U8.with_state(|s: &mut u8, v| { *s = v; v }),
+    ///                U8.with_state(|s: &mut u8, v| *s + v));
+    /// assert_eq!(pattern.sync_read_from(input).unwrap(), (10, 13));
+    /// ```
+    fn with_state<F, T, U>(self, f: F) -> combinators::WithState<Self, F, T>
+    where
+        F: FnOnce(&mut T, Self::Value) -> U,
+    {
+        combinators_impl::with_state(self, f)
+    }
+
+    /// Creates a pattern which computes the checksum `H` of
+    /// the bytes consumed (or produced) by `self`.
+    ///
+    /// The value of the resulting pattern is a tuple of the value of `self` and the checksum.
+    /// To verify (or append) a trailing checksum field,
+    /// use `Checksummed::verify` (or `Checksummed::append`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate futures;
+    /// # extern crate handy_async;
+    /// use futures::Future;
+    /// use handy_async::checksum::Crc16Ccitt;
+    /// use handy_async::io::{ReadFrom, WriteInto};
+    /// use handy_async::pattern::{Pattern, Endian};
+    /// use handy_async::pattern::read::{U8, U16};
+    ///
+    /// # fn main() {
+    /// // Writes a frame followed by its checksum.
+    /// let frame = (1u8, 2u16.be()).checksummed::<Crc16Ccitt>().append(|c: u16| c.be());
+    /// let (output, _) = frame.write_into(Vec::new()).wait().unwrap();
+    /// assert_eq!(output, [1, 0, 2, 0xDB, 0xEE]);
+    ///
+    /// // Reads the frame.
+    /// let frame = || (U8, U16.be()).checksummed::<Crc16Ccitt>();
+    /// let (_, value) = frame().read_from(&output[..]).wait().unwrap();
+    /// assert_eq!(value, ((1, 2), 0xDBEE));
+    ///
+    /// let (_, value) = frame().verify(U16.be()).read_from(&output[..]).wait().unwrap();
+    /// assert_eq!(value, (1, 2));
+    ///
+    /// // Corrupted.
+    /// let input = [1, 0, 3, 0xDB, 0xEE];
+    /// assert!(frame().verify(U16.be()).read_from(&input[..]).wait().is_err());
+    /// # }
+    /// ```
+    fn checksummed<H: Checksum>(self) -> combinators::Checksummed<Self, H> {
+        combinators_impl::checksummed(self)
+    }
+
     /// Returnes a boxed pattern to match with a matcher `M`.
     fn boxed<M: Matcher>(self) -> BoxPattern<M, Self::Value>
     where
@@ -127,6 +538,45 @@ pub trait Pattern: Sized {
         let mut f = Some(move |matcher: M| Box::new(self.async_match(matcher)));
         BoxPattern(Box::new(move |matcher| (f.take().unwrap())(matcher)))
     }
+
+    /// Returnes a boxed pattern to match with a matcher `M`.
+    ///
+    /// Unlike `Pattern::boxed`, the future of the pattern is not required to be `Send`.
+    /// This is useful for single-threaded executors.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate futures;
+    /// # extern crate handy_async;
+    /// use std::cell::Cell;
+    /// use std::rc::Rc;
+    /// use futures::Future;
+    /// use handy_async::pattern::Pattern;
+    /// use handy_async::pattern::read::U8;
+    /// use handy_async::io::ReadFrom;
+    ///
+    /// # fn main() {
+    /// let count = Rc::new(Cell::new(0));
+    /// let count_clone = count.clone();
+    /// let pattern = U8.map(move |b| {
+    ///     count_clone.set(count_clone.get() + 1);
+    ///     b
+    /// });
+    ///
+    /// let (_, value) = pattern.boxed_local().read_from(&[3][..]).wait().unwrap();
+    /// assert_eq!(value, 3);
+    /// assert_eq!(count.get(), 1);
+    /// # }
+    /// ```
+    fn boxed_local<M: Matcher>(self) -> LocalBoxPattern<M, Self::Value>
+    where
+        Self: AsyncMatch<M> + 'static,
+        Self::Future: 'static,
+    {
+        let mut f = Some(move |matcher: M| Box::new(self.async_match(matcher)));
+        LocalBoxPattern(Box::new(move |matcher| (f.take().unwrap())(matcher)))
+    }
 }
 
 /// Boxed pattern.
@@ -142,6 +592,73 @@ impl<M: Matcher, T> AsyncMatch<M> for BoxPattern<M, T> {
 }
 type BoxMatchFn<M, T, E> = Box<FnMut(M) -> BoxFuture<(M, T), AsyncError<M, E>>>;
 
+/// Boxed pattern whose future is not required to be `Send`.
+///
+/// This pattern is created by calling `Pattern::boxed_local` method.
+pub struct LocalBoxPattern<M: Matcher, T>(LocalBoxMatchFn<M, T, M::Error>);
+impl<M: Matcher, T> Pattern for LocalBoxPattern<M, T> {
+    type Value = T;
+}
+impl<M: Matcher, T> AsyncMatch<M> for LocalBoxPattern<M, T> {
+    type Future = LocalBoxFuture<(M, T), AsyncError<M, M::Error>>;
+    fn async_match(mut self, matcher: M) -> Self::Future {
+        (self.0)(matcher)
+    }
+}
+type LocalBoxMatchFn<M, T, E> = Box<dyn FnMut(M) -> LocalBoxFuture<(M, T), AsyncError<M, E>>>;
+
+/// Cloneable boxed pattern built from a pattern factory.
+///
+/// Every match calls the factory to make a fresh pattern,
+/// so this pattern can be reused (e.g., with `ReadFrom::into_stream`)
+/// even if the underlying pattern is not `Clone`.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate futures;
+/// # extern crate handy_async;
+/// use futures::{Future, Stream};
+/// use handy_async::pattern::{Pattern, ArcPattern};
+/// use handy_async::pattern::read::U8;
+/// use handy_async::io::ReadFrom;
+///
+/// # fn main() {
+/// // `AndThen` is not `Clone`.
+/// let pattern = ArcPattern::new(|| U8.and_then(|n| vec![0; n as usize]));
+/// let input: &'static [u8] = &[1, 7, 2, 8, 9, 0];
+/// let values = pattern.into_stream(input).take(3).collect().wait().unwrap();
+/// assert_eq!(values, [vec![7], vec![8, 9], vec![]]);
+/// # }
+/// ```
+pub struct ArcPattern<M: Matcher, T>(ArcMatchFn<M, T, M::Error>);
+impl<M: Matcher, T> ArcPattern<M, T> {
+    /// Makes new `ArcPattern` instance which makes patterns by calling `factory`.
+    pub fn new<F, P>(factory: F) -> Self
+    where
+        F: Fn() -> P + Send + Sync + 'static,
+        P: AsyncMatch<M, Value = T>,
+        P::Future: Send + 'static,
+    {
+        ArcPattern(Arc::new(move |matcher| Box::new(factory().async_match(matcher))))
+    }
+}
+impl<M: Matcher, T> Clone for ArcPattern<M, T> {
+    fn clone(&self) -> Self {
+        ArcPattern(self.0.clone())
+    }
+}
+impl<M: Matcher, T> Pattern for ArcPattern<M, T> {
+    type Value = T;
+}
+impl<M: Matcher, T> AsyncMatch<M> for ArcPattern<M, T> {
+    type Future = BoxFuture<(M, T), AsyncError<M, M::Error>>;
+    fn async_match(self, matcher: M) -> Self::Future {
+        (self.0)(matcher)
+    }
+}
+type ArcMatchFn<M, T, E> = Arc<dyn Fn(M) -> BoxFuture<(M, T), AsyncError<M, E>> + Send + Sync>;
+
 /// A pattern which represents a sequence of a pattern `P`.
 #[derive(Debug)]
 pub struct Iter<I>(pub I);
@@ -158,6 +675,61 @@ where
     {
         combinators_impl::iter_fold(self.0, f, init)
     }
+
+    /// Creates `IterCollect` combinator to collect the values of
+    /// the patterns contained in the iterator `I` into a collection `C`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::BTreeMap;
+    /// use handy_async::io::ReadFrom;
+    /// use handy_async::pattern::{Pattern, Iter, Endian};
+    /// use handy_async::pattern::read::{U8, U16};
+    ///
+    /// // A table of two (key, value) records.
+    /// let pattern = Iter(vec![(U8, U16.be()); 2].into_iter()).collect::<BTreeMap<_, _>>();
+    /// let table = pattern.sync_read_from(&[1, 0, 10, 2, 0, 20][..]).unwrap();
+    /// assert_eq!(table.get(&1), Some(&10));
+    /// assert_eq!(table.get(&2), Some(&20));
+    /// ```
+    pub fn collect<C>(self) -> combinators::IterCollect<I, C>
+    where
+        C: FromIterator<P::Value>,
+    {
+        combinators_impl::iter_collect(self.0)
+    }
+
+    /// Creates `IterTryFold` combinator to fold the values of
+    /// the patterns contained in the iterator `I` with a fallible function.
+    ///
+    /// If `f` returns an error, the matching will be aborted immediately
+    /// and the error will be converted into the matcher's error type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::io::{Error, ErrorKind};
+    /// use handy_async::io::ReadFrom;
+    /// use handy_async::pattern::{Pattern, Iter};
+    /// use handy_async::pattern::read::U8;
+    ///
+    /// let checked_sum = |sum: u8, n: u8| {
+    ///     sum.checked_add(n).ok_or_else(|| Error::new(ErrorKind::InvalidData, "Overflow"))
+    /// };
+    ///
+    /// let pattern = Iter(vec![U8; 3].into_iter()).try_fold(0, checked_sum);
+    /// assert_eq!(pattern.sync_read_from(&[1, 2, 3][..]).unwrap(), 6);
+    ///
+    /// let pattern = Iter(vec![U8; 3].into_iter()).try_fold(0, checked_sum);
+    /// assert!(pattern.sync_read_from(&[1, 255, 3][..]).is_err());
+    /// ```
+    pub fn try_fold<F, T, E>(self, init: T, f: F) -> combinators::IterTryFold<I, F, T>
+    where
+        F: Fn(T, P::Value) -> Result<T, E>,
+    {
+        combinators_impl::iter_try_fold(self.0, f, init)
+    }
 }
 impl<I, P> Pattern for Iter<I>
 where
@@ -207,6 +779,88 @@ impl_tuple_pattern!(P0, P1, P2, P3, P4, P5, P6);
 impl_tuple_pattern!(P0, P1, P2, P3, P4, P5, P6, P7);
 impl_tuple_pattern!(P0, P1, P2, P3, P4, P5, P6, P7, P8);
 impl_tuple_pattern!(P0, P1, P2, P3, P4, P5, P6, P7, P8, P9);
+impl_tuple_pattern!(P0, P1, P2, P3, P4, P5, P6, P7, P8, P9, P10);
+impl_tuple_pattern!(P0, P1, P2, P3, P4, P5, P6, P7, P8, P9, P10, P11);
+impl_tuple_pattern!(P0, P1, P2, P3, P4, P5, P6, P7, P8, P9, P10, P11, P12);
+impl_tuple_pattern!(P0, P1, P2, P3, P4, P5, P6, P7, P8, P9, P10, P11, P12, P13);
+impl_tuple_pattern!(P0, P1, P2, P3, P4, P5, P6, P7, P8, P9, P10, P11, P12, P13, P14);
+impl_tuple_pattern!(P0, P1, P2, P3, P4, P5, P6, P7, P8, P9, P10, P11, P12, P13, P14, P15);
+
+/// Array pattern.
+///
+/// The patterns contained in the array are matched sequentially,
+/// and the resulting values are returned as an array.
+///
+/// # Examples
+///
+/// ```
+/// use handy_async::io::{ReadFrom, WriteInto};
+/// use handy_async::pattern::{Pattern, Endian};
+/// use handy_async::pattern::read::U16;
+///
+/// let pattern = [U16.be(), U16.be(), U16.be()];
+/// assert_eq!(pattern.sync_read_from(&[0, 1, 0, 2, 0, 3][..]).unwrap(), [1, 2, 3]);
+///
+/// let mut buf = Vec::new();
+/// [1u8, 2, 3].sync_write_into(&mut buf).unwrap();
+/// assert_eq!(buf, [1, 2, 3]);
+/// ```
+impl<P: Pattern, const N: usize> Pattern for [P; N] {
+    type Value = [P::Value; N];
+}
+
+/// A pattern which represents a sequence of patterns.
+///
+/// The patterns contained in the vector are matched sequentially,
+/// and the resulting values are returned as a `Vec`.
+///
+/// This wrapper is needed because `Vec<P>` cannot implement `Pattern` for
+/// arbitrary `P`: `Vec<u8>` is already a pattern which represents a byte buffer,
+/// and a blanket implementation for `Vec<P>` would overlap with it.
+/// `VecDeque<P>` has no such conflict and is a sequence pattern by itself.
+///
+/// # Examples
+///
+/// ```
+/// use handy_async::io::{ReadFrom, WriteInto};
+/// use handy_async::pattern::{Pattern, Endian, Seq};
+/// use handy_async::pattern::read::U16;
+///
+/// let pattern = Seq(vec![U16.be(); 3]);
+/// assert_eq!(pattern.sync_read_from(&[0, 1, 0, 2, 0, 3, 0][..]).unwrap(), [1, 2, 3]);
+///
+/// let mut buf = Vec::new();
+/// Seq(vec![1u16.be(), 2u16.be()]).sync_write_into(&mut buf).unwrap();
+/// assert_eq!(buf, [0, 1, 0, 2]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Seq<P>(pub Vec<P>);
+impl<P: Pattern> Pattern for Seq<P> {
+    type Value = Vec<P::Value>;
+}
+
+/// `VecDeque` pattern.
+///
+/// The patterns contained in the queue are matched sequentially,
+/// and the resulting values are returned as a `Vec`.
+///
+/// Note that `Vec<P>` cannot be a pattern of this kind,
+/// because `Vec<u8>` is already a pattern which represents a byte buffer.
+/// Use [Seq](./struct.Seq.html) to match a `Vec` of patterns.
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::VecDeque;
+/// use handy_async::io::ReadFrom;
+/// use handy_async::pattern::read::U8;
+///
+/// let pattern = vec![U8; 3].into_iter().collect::<VecDeque<_>>();
+/// assert_eq!(pattern.sync_read_from(&[1, 2, 3, 4][..]).unwrap(), [1, 2, 3]);
+/// ```
+impl<P: Pattern> Pattern for VecDeque<P> {
+    type Value = Vec<P::Value>;
+}
 
 /// A pattern which represents branches in a pattern.
 ///
@@ -283,6 +937,210 @@ where
     }
 }
 
+/// A pattern which reads a tag with the pattern `P` and
+/// dispatches to the arm registered for the tag.
+///
+/// Any number of arms can be registered.
+/// Each arm is registered as a function which builds the pattern,
+/// and only the arm selected by the tag is built when it is matched.
+/// All arms must have the same type `A`;
+/// use `BoxPattern` (via `Pattern::boxed`) for arbitrary patterns,
+/// or your own enum (e.g., `Branch`) to avoid allocations.
+///
+/// If no arm is registered for a tag, the default arm is used if it exists.
+/// Otherwise the matching fails with the `UnknownTag` error.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate futures;
+/// # extern crate handy_async;
+/// use futures::Future;
+/// use handy_async::io::ReadFrom;
+/// use handy_async::pattern::{Pattern, Endian, Switch};
+/// use handy_async::pattern::read::{U8, U16, Utf8};
+///
+/// #[derive(Debug, PartialEq)]
+/// enum Message {
+///     Ping,
+///     Data(u16),
+///     Text(String),
+///     Unknown(u8),
+/// }
+///
+/// # fn main() {
+/// let message = Switch::new(U8)
+///     .arm(0, || Ok(Message::Ping).boxed())
+///     .arm(1, || U16.be().map(Message::Data).boxed())
+///     .arm(2, || Utf8(U8.and_then(|n| vec![0; n as usize])).map(Message::Text).boxed())
+///     .default(|tag| Ok(Message::Unknown(tag)).boxed());
+///
+/// let input: &'static [u8] = &[1, 0, 3, 2, 2, b'h', b'i', 9];
+/// let pattern = (message.clone(), message.clone(), message);
+/// let (_, value) = pattern.read_from(input).wait().unwrap();
+/// assert_eq!(value,
+///            (Message::Data(3), Message::Text("hi".to_string()), Message::Unknown(9)));
+///
+/// // Without the default arm, unknown tags result in an error.
+/// let pattern = Switch::new(U8).arm(0, || Ok(Message::Ping).boxed());
+/// assert!(pattern.read_from(&[9][..]).wait().is_err());
+/// # }
+/// ```
+pub struct Switch<P: Pattern, A> {
+    tag: P,
+    arms: HashMap<P::Value, SwitchArm<A>>,
+    default: Option<DefaultArm<P::Value, A>>,
+}
+impl<P: Pattern, A> Switch<P, A>
+where
+    P::Value: Eq + Hash,
+{
+    /// Makes new `Switch` pattern which reads a tag with the pattern `tag`.
+    pub fn new(tag: P) -> Self {
+        Switch {
+            tag,
+            arms: HashMap::new(),
+            default: None,
+        }
+    }
+
+    /// Registers the arm for the tag `tag`.
+    ///
+    /// The function `f` will be called to build the arm when the tag is read.
+    ///
+    /// If an arm has already been registered for the tag, it will be replaced.
+    pub fn arm<F>(mut self, tag: P::Value, f: F) -> Self
+    where
+        F: Fn() -> A + Send + Sync + 'static,
+    {
+        self.arms.insert(tag, Arc::new(f));
+        self
+    }
+
+    /// Sets the default arm which is used for tags having no registered arms.
+    ///
+    /// The function `f` will be called with the unknown tag.
+    pub fn default<F>(mut self, f: F) -> Self
+    where
+        F: Fn(P::Value) -> A + Send + Sync + 'static,
+    {
+        self.default = Some(Arc::new(f));
+        self
+    }
+
+    #[allow(missing_docs)]
+    #[cfg_attr(feature = "cargo-clippy", allow(type_complexity))]
+    pub fn unwrap(self) -> (P, HashMap<P::Value, SwitchArm<A>>, Option<DefaultArm<P::Value, A>>) {
+        (self.tag, self.arms, self.default)
+    }
+}
+impl<P: Pattern, A: Pattern> Pattern for Switch<P, A> {
+    type Value = A::Value;
+}
+impl<P, A> Clone for Switch<P, A>
+where
+    P: Pattern + Clone,
+    P::Value: Clone,
+{
+    fn clone(&self) -> Self {
+        Switch {
+            tag: self.tag.clone(),
+            arms: self.arms.clone(),
+            default: self.default.clone(),
+        }
+    }
+}
+
+/// The function which builds an arm of a `Switch` pattern.
+pub type SwitchArm<A> = Arc<dyn Fn() -> A + Send + Sync>;
+
+/// The function which builds the default arm of a `Switch` pattern from an unknown tag.
+pub type DefaultArm<K, A> = Arc<dyn Fn(K) -> A + Send + Sync>;
+
+/// A pattern which constructs the actual pattern by calling `F` when it is matched.
+///
+/// This is useful to describe recursive grammars:
+/// a function which returns a `BoxPattern` can refer to itself via `Lazy`.
+///
+/// The nesting depth of `Lazy` patterns being matched is limited
+/// (`128` by default, configurable by `Lazy::max_depth`).
+/// If the limit is exceeded, the matching fails with the `RecursionLimitExceeded` error.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate futures;
+/// # extern crate handy_async;
+/// use futures::Future;
+/// use handy_async::io::{ReadFrom, PatternReader};
+/// use handy_async::pattern::{Pattern, BoxPattern, Lazy};
+/// use handy_async::pattern::read::U8;
+///
+/// #[derive(Debug, PartialEq)]
+/// enum Tree {
+///     Leaf(u8),
+///     Node(Box<Tree>, Box<Tree>),
+/// }
+///
+/// fn tree() -> BoxPattern<PatternReader<&'static [u8]>, Tree> {
+///     U8.and_then(|tag| if tag == 0 {
+///             U8.map(Tree::Leaf).boxed()
+///         } else {
+///             (Lazy::new(tree), Lazy::new(tree))
+///                 .map(|(l, r)| Tree::Node(Box::new(l), Box::new(r)))
+///                 .boxed()
+///         })
+///         .boxed()
+/// }
+///
+/// # fn main() {
+/// let input: &'static [u8] = &[1, 0, 1, 1, 0, 2, 0, 3];
+/// let (_, value) = Lazy::new(tree).read_from(input).wait().unwrap();
+/// assert_eq!(value,
+///            Tree::Node(Box::new(Tree::Leaf(1)),
+///                       Box::new(Tree::Node(Box::new(Tree::Leaf(2)),
+///                                           Box::new(Tree::Leaf(3))))));
+///
+/// // Too deep.
+/// let input: &'static [u8] = &[1, 1, 1, 0, 1, 0, 2, 0, 3];
+/// assert!(Lazy::new(tree).max_depth(3).read_from(input).wait().is_err());
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Lazy<F> {
+    f: F,
+    max_depth: usize,
+}
+impl<F> Lazy<F> {
+    /// Makes new `Lazy` pattern which constructs the actual pattern by calling `f`.
+    pub fn new(f: F) -> Self {
+        Lazy {
+            f,
+            max_depth: 128,
+        }
+    }
+
+    /// Sets the maximum nesting depth of `Lazy` patterns (including this one).
+    ///
+    /// Nested `Lazy` patterns can not raise the limit set by the enclosing ones.
+    pub fn max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    #[allow(missing_docs)]
+    pub fn unwrap(self) -> (F, usize) {
+        (self.f, self.max_depth)
+    }
+}
+impl<F, P> Pattern for Lazy<F>
+where
+    F: FnOnce() -> P,
+    P: Pattern,
+{
+    type Value = P::Value;
+}
+
 /// A trait to indicate that a pattern is partially evaluable.
 pub trait AllowPartial: Sized {
     /// Indicates that this pattern is partially evaluable.
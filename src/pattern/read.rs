@@ -154,6 +154,9 @@ impl Pattern for Eos {
 }
 
 /// A pattern which continues reading until the predicate `F` is satisfied.
+///
+/// The predicate re-examines the whole buffer after each read operation;
+/// see [until](./fn.until.html) for details.
 #[derive(Debug)]
 pub struct Until<F, T> {
     pred: F,
@@ -191,6 +194,11 @@ impl<F, T> Pattern for Until<F, T> {
 /// `is_eos` flag (the second argument).
 ///
 /// If `is_eos == true` and `F` returns `Ok(None)`, it will result in the `UnexpectedEof` error.
+///
+/// Note that `F` is called with the whole buffer after each read operation,
+/// so the total cost of the scanning is quadratic in the number of reads
+/// if `F` inspects every byte (e.g., when the input arrives in small chunks).
+/// To find the end of bytes incrementally, use [Scan](./struct.Scan.html) instead.
 pub fn until<F, T>(f: F) -> Until<F, T>
 where
     F: Fn(&[u8], bool) -> io::Result<Option<T>>,
@@ -203,6 +211,209 @@ where
     }
 }
 
+/// A stateful scanner used by the `Scan` pattern to find the end of a byte sequence.
+///
+/// Unlike the predicate of `Until`, a scanner is fed only the newly read bytes,
+/// so the bytes which have been scanned once are never scanned again.
+pub trait Scanner {
+    /// Scans `new_bytes` which have been newly read.
+    ///
+    /// `offset` is the position of `new_bytes[0]` in the whole bytes scanned so far.
+    /// If the end is found, this returns the end position (exclusive) in the whole bytes.
+    fn scan(&mut self, new_bytes: &[u8], offset: usize) -> Option<usize>;
+}
+impl Scanner for u8 {
+    fn scan(&mut self, new_bytes: &[u8], offset: usize) -> Option<usize> {
+        new_bytes.iter().position(|b| b == self).map(|i| offset + i + 1)
+    }
+}
+impl<F> Scanner for F
+where
+    F: FnMut(&[u8], usize) -> Option<usize>,
+{
+    fn scan(&mut self, new_bytes: &[u8], offset: usize) -> Option<usize> {
+        self(new_bytes, offset)
+    }
+}
+
+/// A scanner which finds the first occurrence of a byte sequence.
+///
+/// The occurrence which straddles the boundary of two reads is also found.
+/// The search is done by the Knuth-Morris-Pratt algorithm,
+/// so each byte is scanned in amortized constant time regardless of the needle.
+///
+/// # Examples
+///
+/// ```
+/// use handy_async::pattern::read::{ByteSeq, Scanner};
+///
+/// let mut scanner = ByteSeq::new(b"\r\n".to_vec());
+/// assert_eq!(scanner.scan(b"foo\r", 0), None);
+/// assert_eq!(scanner.scan(b"\nbar", 4), Some(5));
+///
+/// let mut scanner = ByteSeq::new(b"aaab".to_vec());
+/// assert_eq!(scanner.scan(b"aaaaa", 0), None);
+/// assert_eq!(scanner.scan(b"ab", 5), Some(7));
+/// ```
+#[derive(Debug, Clone)]
+pub struct ByteSeq {
+    needle: Vec<u8>,
+    failure: Vec<usize>,
+    matched: usize,
+}
+impl ByteSeq {
+    /// Makes new `ByteSeq` scanner which finds `needle`.
+    ///
+    /// # Panics
+    ///
+    /// `needle` must not be empty.
+    pub fn new(needle: Vec<u8>) -> Self {
+        assert!(!needle.is_empty());
+
+        // `failure[i]` is the length of the longest proper prefix of `needle[..=i]`
+        // which is also a suffix of it.
+        let mut failure = vec![0; needle.len()];
+        let mut k = 0;
+        for i in 1..needle.len() {
+            while k > 0 && needle[i] != needle[k] {
+                k = failure[k - 1];
+            }
+            if needle[i] == needle[k] {
+                k += 1;
+            }
+            failure[i] = k;
+        }
+        ByteSeq {
+            needle,
+            failure,
+            matched: 0,
+        }
+    }
+}
+impl Scanner for ByteSeq {
+    fn scan(&mut self, new_bytes: &[u8], offset: usize) -> Option<usize> {
+        for (i, &b) in new_bytes.iter().enumerate() {
+            while self.matched > 0 && b != self.needle[self.matched] {
+                self.matched = self.failure[self.matched - 1];
+            }
+            if b == self.needle[self.matched] {
+                self.matched += 1;
+            }
+            if self.matched == self.needle.len() {
+                self.matched = 0;
+                return Some(offset + i + 1);
+            }
+        }
+        None
+    }
+}
+
+/// A pattern which continues reading until the scanner `S` finds the end.
+///
+/// The value of this pattern is the bytes up to the end found by the scanner.
+/// The bytes which follow the end are not consumed.
+///
+/// # Examples
+///
+/// ```
+/// use handy_async::io::ReadFrom;
+/// use handy_async::pattern::read::{ByteSeq, Scan, U8};
+///
+/// let pattern = (Scan::new(b';'), U8);
+/// assert_eq!(pattern.sync_read_from(&b"foo;bar"[..]).unwrap(), (b"foo;".to_vec(), b'b'));
+///
+/// let pattern = Scan::new(ByteSeq::new(b"\r\n".to_vec())).chunk_size(2);
+/// assert_eq!(pattern.sync_read_from(&b"foo\r\nbar"[..]).unwrap(), b"foo\r\n");
+///
+/// // Unexpected EOF.
+/// assert!(Scan::new(b';').sync_read_from(&b"foo"[..]).is_err());
+///
+/// // Size limit exceeded.
+/// assert!(Scan::new(b';').max_size(2).sync_read_from(&b"foo;"[..]).is_err());
+/// ```
+#[derive(Debug, Clone)]
+pub struct Scan<S> {
+    scanner: S,
+    chunk_size: usize,
+    max_size: usize,
+}
+impl<S: Scanner> Scan<S> {
+    /// Makes new `Scan` pattern.
+    pub fn new(scanner: S) -> Self {
+        Scan {
+            scanner,
+            chunk_size: 1024,
+            max_size: 10 * 1024 * 1024,
+        }
+    }
+
+    /// Sets the size of the buffer used for each read operation.
+    ///
+    /// If `size` is `0`, it is regarded as `1`.
+    pub fn chunk_size(mut self, size: usize) -> Self {
+        self.chunk_size = ::std::cmp::max(size, 1);
+        self
+    }
+
+    /// Sets the maximum byte size allowed to scan.
+    pub fn max_size(mut self, size: usize) -> Self {
+        self.max_size = size;
+        self
+    }
+}
+impl<S> Scan<S> {
+    #[allow(missing_docs)]
+    pub fn unwrap(self) -> (S, usize, usize) {
+        (self.scanner, self.chunk_size, self.max_size)
+    }
+}
+impl<S> Pattern for Scan<S> {
+    type Value = Vec<u8>;
+}
+
+/// A pattern which applies `F` to the state of the `Stateful` stream being read.
+///
+/// This pattern does not consume any bytes.
+///
+/// # Examples
+///
+/// ```
+/// use handy_async::io::{ReadFrom, Stateful};
+/// use handy_async::pattern::read::{self, U8};
+///
+/// let input = Stateful { stream: &[3][..], state: 10 };
+/// let pattern = (read::state(|s: &mut u32| { *s += 1; *s }), U8);
+/// assert_eq!(pattern.sync_read_from(input).unwrap(), (11, 3));
+/// ```
+#[derive(Debug)]
+pub struct State<F, T> {
+    f: F,
+    _phantom: PhantomData<T>,
+}
+impl<F, T> State<F, T> {
+    #[allow(missing_docs)]
+    pub fn unwrap(self) -> F {
+        self.f
+    }
+}
+impl<F, T, V> Pattern for State<F, T>
+where
+    F: FnOnce(&mut T) -> V,
+{
+    type Value = V;
+}
+
+/// Makes `State` pattern which applies `f` to the state of the `Stateful` stream.
+pub fn state<F, T, V>(f: F) -> State<F, T>
+where
+    F: FnOnce(&mut T) -> V,
+{
+    State {
+        f,
+        _phantom: PhantomData,
+    }
+}
+
 /// A pattern which indicates a line string.
 ///
 /// A line is ended with a newline character `\n`.
@@ -241,3 +452,157 @@ where
 {
     type Value = String;
 }
+
+/// A pattern which matches the inner pattern without consuming the read bytes.
+///
+/// The bytes read during the matching are pushed back into the reader,
+/// and will be read again by the subsequent patterns.
+/// This applies to both successful and failed matchings.
+///
+/// This pattern is only available for `PatternReader` (`TracingReader` does not support it).
+///
+/// # Examples
+///
+/// ```
+/// use handy_async::io::ReadFrom;
+/// use handy_async::pattern::{Pattern, Endian};
+/// use handy_async::pattern::read::{Peek, U8, U16};
+///
+/// let pattern = (Peek(U8), U16.be());
+/// assert_eq!(pattern.sync_read_from(&[1, 2][..]).unwrap(), (1, 0x0102));
+///
+/// let pattern = (Peek(U8.expect_eq(0)).or(U8), U8);
+/// assert_eq!(pattern.sync_read_from(&[1, 2][..]).unwrap().1, 2);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Peek<P>(pub P);
+impl<P: Pattern> Pattern for Peek<P> {
+    type Value = P::Value;
+}
+
+/// A pattern which discards the specified number of bytes.
+///
+/// The bytes are read in fixed size chunks, so this does not allocate a buffer of that size.
+///
+/// # Examples
+///
+/// ```
+/// use handy_async::io::ReadFrom;
+/// use handy_async::pattern::read::{Skip, U8};
+///
+/// let input = (0..200).collect::<Vec<u8>>();
+/// let pattern = (U8, Skip(198), U8);
+/// assert_eq!(pattern.sync_read_from(&input[..]).unwrap(), (0, (), 199));
+///
+/// // Unexpected EOF.
+/// assert!(Skip(3).sync_read_from(&[0, 0][..]).is_err());
+/// ```
+#[derive(Debug, Clone)]
+pub struct Skip(pub usize);
+impl Pattern for Skip {
+    type Value = ();
+}
+
+/// A pattern which discards bytes until the offset of the matcher
+/// becomes a multiple of the specified alignment.
+///
+/// The alignment `0` is regarded as `1` (i.e., nothing is discarded).
+///
+/// # Examples
+///
+/// ```
+/// use handy_async::io::ReadFrom;
+/// use handy_async::pattern::read::{Align, U8};
+///
+/// let pattern = (U8, Align(4), U8, Align(4));
+/// assert_eq!(pattern.sync_read_from(&[1, 0, 0, 0, 2, 0, 0, 0][..]).unwrap(),
+///            (1, (), 2, ()));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Align(pub u64);
+impl Pattern for Align {
+    type Value = ();
+}
+
+/// A pattern which seeks the reader to the specified position.
+///
+/// The value of this pattern is the new position from the start of the stream.
+///
+/// # Examples
+///
+/// ```
+/// use std::io::{Cursor, SeekFrom};
+/// use handy_async::io::ReadFrom;
+/// use handy_async::pattern::read::{SeekTo, U8};
+///
+/// let pattern = (SeekTo(SeekFrom::End(-1)), U8);
+/// assert_eq!(pattern.sync_read_from(Cursor::new(vec![1, 2, 3])).unwrap(), (2, 3));
+/// ```
+#[derive(Debug, Clone)]
+pub struct SeekTo(pub io::SeekFrom);
+impl Pattern for SeekTo {
+    type Value = u64;
+}
+
+/// A pattern which matches the pattern `P` at the specified offset (from the start of the stream).
+///
+/// After the matching, the reader is restored to the position before the seeking.
+/// To jump without restoring, use `SeekTo` instead.
+///
+/// # Examples
+///
+/// ```
+/// use std::io::Cursor;
+/// use handy_async::io::ReadFrom;
+/// use handy_async::pattern::Pattern;
+/// use handy_async::pattern::read::{At, U8};
+///
+/// // The first byte is the offset of the data.
+/// let pattern = (U8.and_then(|offset| At(offset as u64, U8)), U8);
+/// assert_eq!(pattern.sync_read_from(Cursor::new(vec![3, 1, 2, 3])).unwrap(), (3, 1));
+/// ```
+#[derive(Debug, Clone)]
+pub struct At<P>(pub u64, pub P);
+impl<P: Pattern> Pattern for At<P> {
+    type Value = P::Value;
+}
+
+/// A pattern which returns the current offset of the reader without consuming any bytes.
+///
+/// # Examples
+///
+/// ```
+/// use handy_async::io::ReadFrom;
+/// use handy_async::pattern::read::{Position, U8, U16};
+///
+/// let pattern = (U8, Position, U16, Position);
+/// assert_eq!(pattern.sync_read_from(&[0, 1, 2][..]).unwrap(), (0, 1, 0x0201, 3));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Position;
+impl Pattern for Position {
+    type Value = u64;
+}
+
+/// A pattern which reads the bytes of the fixed size pattern `P` by a single read operation,
+/// and then decodes the value.
+///
+/// This is more efficient than reading the fields of `P` one by one
+/// (see `benches/fixed.rs`; run it by `cargo bench`).
+///
+/// # Examples
+///
+/// ```
+/// use handy_async::io::ReadFrom;
+/// use handy_async::pattern::Endian;
+/// use handy_async::pattern::read::{Fixed, U16, U32};
+///
+/// let pattern = Fixed((U16.be(), U16.be(), U32.be(), U32.be()));
+/// let input = [0, 1, 0, 2, 0, 0, 0, 3, 0, 0, 0, 4];
+/// assert_eq!(pattern.sync_read_from(&input[..]).unwrap(), (1, 2, 3, 4));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Fixed<P>(pub P);
+impl<P: Pattern> Pattern for Fixed<P> {
+    type Value = P::Value;
+}
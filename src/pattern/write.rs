@@ -1,4 +1,7 @@
 //! Patterns specific to writing operation.
+use std::io;
+use std::marker::PhantomData;
+
 use super::{Pattern, Endian};
 
 /// A pattern which indicates to flush internal buffer.
@@ -105,3 +108,142 @@ impl Pattern for I56 {
     type Value = ();
 }
 impl Endian for I56 {}
+
+/// A pattern which writes the byte `self.0` repeatedly `self.1` times.
+///
+/// The bytes are written in fixed size chunks, so this does not allocate a buffer of that size.
+///
+/// # Examples
+///
+/// ```
+/// use handy_async::io::WriteInto;
+/// use handy_async::pattern::write::Fill;
+///
+/// let mut output = Vec::new();
+/// (1u8, Fill(0xFF, 3)).sync_write_into(&mut output).unwrap();
+/// assert_eq!(output, [1, 0xFF, 0xFF, 0xFF]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Fill(pub u8, pub usize);
+impl Pattern for Fill {
+    type Value = ();
+}
+
+/// A pattern which writes the byte `self.1` until the offset of the matcher
+/// becomes a multiple of the alignment `self.0`.
+///
+/// The alignment `0` is regarded as `1` (i.e., nothing is written).
+///
+/// # Examples
+///
+/// ```
+/// use handy_async::io::WriteInto;
+/// use handy_async::pattern::write::Align;
+///
+/// let mut output = Vec::new();
+/// (1u8, Align(4, 0), 2u8, Align(4, 0)).sync_write_into(&mut output).unwrap();
+/// assert_eq!(output, [1, 0, 0, 0, 2, 0, 0, 0]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Align(pub u64, pub u8);
+impl Pattern for Align {
+    type Value = ();
+}
+
+/// A pattern which seeks the writer to the specified position.
+///
+/// The value of this pattern is the new position from the start of the stream.
+#[derive(Debug, Clone)]
+pub struct SeekTo(pub io::SeekFrom);
+impl Pattern for SeekTo {
+    type Value = u64;
+}
+
+/// A pattern which writes the pattern `P` at the specified offset (from the start of the stream).
+///
+/// After the writing, the writer is restored to the position before the seeking.
+/// This is useful to backpatch a field (e.g., a length or an offset) which is known later.
+/// To jump without restoring, use `SeekTo` instead.
+///
+/// # Examples
+///
+/// ```
+/// use std::io::Cursor;
+/// use handy_async::io::WriteInto;
+/// use handy_async::pattern::write::At;
+///
+/// let mut output = Cursor::new(Vec::new());
+/// (0u8, 2u8, 3u8, At(0, 1u8), 4u8).sync_write_into(&mut output).unwrap();
+/// assert_eq!(output.into_inner(), [1, 2, 3, 4]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct At<P>(pub u64, pub P);
+impl<P: Pattern> Pattern for At<P> {
+    type Value = P::Value;
+}
+
+/// A pattern which returns the current offset of the writer without writing any bytes.
+///
+/// # Examples
+///
+/// ```
+/// use handy_async::io::WriteInto;
+/// use handy_async::pattern::write::Position;
+///
+/// let (_, offset, _, offset2) = (1u8, Position, 2u16, Position).sync_write_into(Vec::new()).unwrap();
+/// assert_eq!((offset, offset2), (1, 3));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Position;
+impl Pattern for Position {
+    type Value = u64;
+}
+
+/// A pattern which applies `F` to the state of the `Stateful` stream being written.
+///
+/// This pattern does not consume any bytes.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate futures;
+/// # extern crate handy_async;
+/// use futures::Future;
+/// use handy_async::io::{WriteInto, Stateful};
+/// use handy_async::pattern::write;
+///
+/// # fn main() {
+/// let output = Stateful { stream: Vec::new(), state: 10 };
+/// let pattern = (write::state(|s: &mut u32| { *s += 1; *s }), 3u8);
+/// let (output, (count, _)) = pattern.write_into(output).wait().ok().unwrap();
+/// assert_eq!((output.stream, output.state, count), (vec![3], 11, 11));
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct State<F, T> {
+    f: F,
+    _phantom: PhantomData<T>,
+}
+impl<F, T> State<F, T> {
+    #[allow(missing_docs)]
+    pub fn unwrap(self) -> F {
+        self.f
+    }
+}
+impl<F, T, V> Pattern for State<F, T>
+where
+    F: FnOnce(&mut T) -> V,
+{
+    type Value = V;
+}
+
+/// Makes `State` pattern which applies `f` to the state of the `Stateful` stream.
+pub fn state<F, T, V>(f: F) -> State<F, T>
+where
+    F: FnOnce(&mut T) -> V,
+{
+    State {
+        f,
+        _phantom: PhantomData,
+    }
+}
@@ -0,0 +1,346 @@
+//! A minimal single-threaded reactor and executor.
+//!
+//! This module is available on Unix platforms when the `reactor` feature is enabled.
+//!
+//! The futures in this crate return `NotReady` when the underlying stream
+//! returns the `WouldBlock` error.
+//! By wrapping a non-blocking stream with `Evented`, the task which encountered
+//! the `WouldBlock` error is parked until the stream becomes ready,
+//! and it is resumed by the `Executor` which owns the reactor.
+//!
+//! # Examples
+//!
+//! ```
+//! # extern crate futures;
+//! # extern crate handy_async;
+//! use std::os::unix::net::UnixStream;
+//! use futures::Future;
+//! use handy_async::io::{ReadFrom, WriteInto};
+//! use handy_async::pattern::Endian;
+//! use handy_async::pattern::read::{U8, U16};
+//! use handy_async::reactor::{Evented, Executor};
+//!
+//! # fn main() {
+//! let (tx, rx) = UnixStream::pair().unwrap();
+//! tx.set_nonblocking(true).unwrap();
+//! rx.set_nonblocking(true).unwrap();
+//!
+//! let mut executor = Executor::new().unwrap();
+//! let tx = Evented::new(tx, &executor.handle()).unwrap();
+//! let rx = Evented::new(rx, &executor.handle()).unwrap();
+//!
+//! executor.spawn((1u8, 2u16.be()).write_into(tx).map(|_| ()).map_err(|_| ()));
+//! let future = (U8, U16.be()).read_from(rx).map(|(_, v)| v);
+//! assert_eq!(executor.run_until(future).unwrap().ok(), Some((1, 2)));
+//! # }
+//! ```
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::io::{self, Read, Write, ErrorKind};
+use std::os::unix::io::AsRawFd;
+use std::rc::Rc;
+use std::sync::{Arc, Mutex};
+use futures::{Async, Future};
+use futures::executor::{self, Notify, NotifyHandle, Spawn};
+use futures::task::{self, Task};
+use mio::{Events, Interest, Poll, Token, Waker};
+use mio::unix::SourceFd;
+
+const WAKER_TOKEN: Token = Token(usize::MAX);
+const MAIN_TASK_ID: usize = usize::MAX;
+
+type LocalTask = Spawn<Box<dyn Future<Item = (), Error = ()>>>;
+
+#[derive(Default)]
+struct Waiters {
+    readers: Vec<Task>,
+    writers: Vec<Task>,
+}
+
+struct Reactor {
+    poll: Poll,
+    sources: Vec<Option<Waiters>>,
+    free_tokens: Vec<usize>,
+}
+impl Reactor {
+    fn register<T: AsRawFd>(&mut self, io: &T) -> io::Result<Token> {
+        let index = self.free_tokens.pop().unwrap_or(self.sources.len());
+        let token = Token(index);
+        self.poll.registry().register(
+            &mut SourceFd(&io.as_raw_fd()),
+            token,
+            Interest::READABLE | Interest::WRITABLE,
+        )?;
+        if index == self.sources.len() {
+            self.sources.push(Some(Waiters::default()));
+        } else {
+            self.sources[index] = Some(Waiters::default());
+        }
+        Ok(token)
+    }
+    fn deregister<T: AsRawFd>(&mut self, io: &T, token: Token) {
+        let _ = self.poll.registry().deregister(&mut SourceFd(&io.as_raw_fd()));
+        self.sources[token.0] = None;
+        self.free_tokens.push(token.0);
+    }
+    fn park(&mut self, token: Token, is_read: bool) {
+        if let Some(waiters) = self.sources[token.0].as_mut() {
+            let task = task::current();
+            let tasks = if is_read {
+                &mut waiters.readers
+            } else {
+                &mut waiters.writers
+            };
+            if !tasks.iter().any(|t| t.will_notify_current()) {
+                tasks.push(task);
+            }
+        }
+    }
+    fn turn(&mut self, events: &mut Events) -> io::Result<()> {
+        loop {
+            match self.poll.poll(events, None) {
+                Err(ref e) if e.kind() == ErrorKind::Interrupted => {}
+                result => break result?,
+            }
+        }
+        for event in events.iter() {
+            if event.token() == WAKER_TOKEN {
+                continue;
+            }
+            let waiters = match self.sources.get_mut(event.token().0) {
+                Some(&mut Some(ref mut waiters)) => waiters,
+                _ => continue,
+            };
+            if event.is_readable() || event.is_read_closed() || event.is_error() {
+                for task in waiters.readers.drain(..) {
+                    task.notify();
+                }
+            }
+            if event.is_writable() || event.is_write_closed() || event.is_error() {
+                for task in waiters.writers.drain(..) {
+                    task.notify();
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A handle to the reactor of an `Executor`.
+///
+/// This is used to register streams by `Evented::new`.
+#[derive(Clone)]
+pub struct Handle(Rc<RefCell<Reactor>>);
+
+/// A non-blocking stream registered with a reactor.
+///
+/// If a read (or write) operation returns the `WouldBlock` error,
+/// the current task is parked until the stream becomes readable (or writable).
+/// So the operations must be issued from tasks driven by the `Executor`.
+///
+/// The inner stream `T` is expected to be in non-blocking mode.
+pub struct Evented<T: AsRawFd> {
+    inner: Option<T>,
+    token: Token,
+    handle: Handle,
+}
+impl<T: AsRawFd> Evented<T> {
+    /// Registers `inner` with the reactor of `handle`.
+    pub fn new(inner: T, handle: &Handle) -> io::Result<Self> {
+        let token = handle.0.borrow_mut().register(&inner)?;
+        Ok(Evented {
+            inner: Some(inner),
+            token,
+            handle: handle.clone(),
+        })
+    }
+
+    /// Returns the reference to the inner stream.
+    pub fn inner_ref(&self) -> &T {
+        self.inner.as_ref().expect("Never fails")
+    }
+
+    /// Returns the mutable reference to the inner stream.
+    pub fn inner_mut(&mut self) -> &mut T {
+        self.inner.as_mut().expect("Never fails")
+    }
+
+    /// Deregisters the inner stream from the reactor, and returns it.
+    pub fn into_inner(mut self) -> T {
+        let inner = self.inner.take().expect("Never fails");
+        self.handle.0.borrow_mut().deregister(&inner, self.token);
+        inner
+    }
+
+    fn park_if_would_block<U>(&self, result: &io::Result<U>, is_read: bool) {
+        if let Err(ref e) = *result {
+            if e.kind() == ErrorKind::WouldBlock {
+                self.handle.0.borrow_mut().park(self.token, is_read);
+            }
+        }
+    }
+}
+impl<T: AsRawFd> Drop for Evented<T> {
+    fn drop(&mut self) {
+        if let Some(inner) = self.inner.take() {
+            self.handle.0.borrow_mut().deregister(&inner, self.token);
+        }
+    }
+}
+impl<T: AsRawFd + Read> Read for Evented<T> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let result = self.inner_mut().read(buf);
+        self.park_if_would_block(&result, true);
+        result
+    }
+}
+impl<T: AsRawFd + Write> Write for Evented<T> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let result = self.inner_mut().write(buf);
+        self.park_if_would_block(&result, false);
+        result
+    }
+    fn flush(&mut self) -> io::Result<()> {
+        let result = self.inner_mut().flush();
+        self.park_if_would_block(&result, false);
+        result
+    }
+}
+
+struct ReadyQueue {
+    ids: Mutex<VecDeque<usize>>,
+    waker: Waker,
+}
+impl ReadyQueue {
+    fn push(&self, id: usize) {
+        self.ids.lock().expect("Poisoned").push_back(id);
+    }
+    fn pop(&self) -> Option<usize> {
+        self.ids.lock().expect("Poisoned").pop_front()
+    }
+    fn is_empty(&self) -> bool {
+        self.ids.lock().expect("Poisoned").is_empty()
+    }
+}
+impl Notify for ReadyQueue {
+    fn notify(&self, id: usize) {
+        self.push(id);
+        let _ = self.waker.wake();
+    }
+}
+
+/// A single-threaded executor which drives futures with a reactor.
+pub struct Executor {
+    handle: Handle,
+    tasks: Vec<Option<LocalTask>>,
+    ready: Arc<ReadyQueue>,
+    events: Events,
+}
+impl Executor {
+    /// Makes new `Executor` instance.
+    pub fn new() -> io::Result<Self> {
+        let poll = Poll::new()?;
+        let waker = Waker::new(poll.registry(), WAKER_TOKEN)?;
+        let reactor = Reactor {
+            poll,
+            sources: Vec::new(),
+            free_tokens: Vec::new(),
+        };
+        Ok(Executor {
+            handle: Handle(Rc::new(RefCell::new(reactor))),
+            tasks: Vec::new(),
+            ready: Arc::new(ReadyQueue {
+                ids: Mutex::new(VecDeque::new()),
+                waker,
+            }),
+            events: Events::with_capacity(1024),
+        })
+    }
+
+    /// Returns the handle to the reactor of this executor.
+    pub fn handle(&self) -> Handle {
+        self.handle.clone()
+    }
+
+    /// Spawns a task which will be driven by this executor.
+    pub fn spawn<F>(&mut self, future: F)
+    where
+        F: Future<Item = (), Error = ()> + 'static,
+    {
+        let task = executor::spawn(Box::new(future) as Box<dyn Future<Item = (), Error = ()>>);
+        let id = if let Some(id) = self.tasks.iter().position(Option::is_none) {
+            self.tasks[id] = Some(task);
+            id
+        } else {
+            self.tasks.push(Some(task));
+            self.tasks.len() - 1
+        };
+        self.ready.push(id);
+    }
+
+    /// Runs the spawned tasks until all of them are completed.
+    ///
+    /// When no task is ready, this blocks until a registered stream becomes ready
+    /// or a task is notified (possibly from another thread).
+    /// So if the pending tasks are waiting for something which never happens
+    /// (e.g., a stream which is not registered with this executor), this blocks forever.
+    pub fn run(&mut self) -> io::Result<()> {
+        let notify = NotifyHandle::from(self.ready.clone());
+        loop {
+            self.poll_ready_tasks(&notify);
+            if self.tasks.iter().all(Option::is_none) {
+                return Ok(());
+            }
+            if self.ready.is_empty() {
+                self.turn()?;
+            }
+        }
+    }
+
+    /// Runs the spawned tasks until `future` is completed, and returns the result of it.
+    ///
+    /// Like `Executor::run`, this blocks forever if `future` waits for something
+    /// which never happens.
+    pub fn run_until<F: Future>(&mut self, future: F) -> io::Result<Result<F::Item, F::Error>> {
+        let notify = NotifyHandle::from(self.ready.clone());
+        let mut main = executor::spawn(future);
+        self.ready.push(MAIN_TASK_ID);
+        loop {
+            if self.poll_ready_tasks(&notify) {
+                match main.poll_future_notify(&notify, MAIN_TASK_ID) {
+                    Ok(Async::NotReady) => {}
+                    Ok(Async::Ready(v)) => return Ok(Ok(v)),
+                    Err(e) => return Ok(Err(e)),
+                }
+            }
+            if self.ready.is_empty() {
+                self.turn()?;
+            }
+        }
+    }
+
+    /// Polls the ready tasks, and returns `true` if the main task is ready.
+    fn poll_ready_tasks(&mut self, notify: &NotifyHandle) -> bool {
+        let mut is_main_ready = false;
+        while let Some(id) = self.ready.pop() {
+            if id == MAIN_TASK_ID {
+                is_main_ready = true;
+                continue;
+            }
+            let done = match self.tasks.get_mut(id) {
+                Some(&mut Some(ref mut task)) => {
+                    !matches!(task.poll_future_notify(notify, id), Ok(Async::NotReady))
+                }
+                _ => false,
+            };
+            if done {
+                self.tasks[id] = None;
+            }
+        }
+        is_main_ready
+    }
+
+    fn turn(&mut self) -> io::Result<()> {
+        self.handle.0.borrow_mut().turn(&mut self.events)
+    }
+}
@@ -0,0 +1,89 @@
+#![cfg(all(unix, feature = "reactor"))]
+extern crate futures;
+extern crate handy_async;
+
+use std::cell::Cell;
+use std::net::{TcpListener, TcpStream};
+use std::os::unix::net::UnixStream;
+use std::rc::Rc;
+use futures::Future;
+use handy_async::io::{ReadFrom, WriteInto};
+use handy_async::pattern::Endian;
+use handy_async::pattern::read::{U8, U32};
+use handy_async::reactor::{Evented, Executor, Handle};
+
+fn pair(handle: &Handle) -> (Evented<UnixStream>, Evented<UnixStream>) {
+    let (tx, rx) = UnixStream::pair().unwrap();
+    tx.set_nonblocking(true).unwrap();
+    rx.set_nonblocking(true).unwrap();
+    (Evented::new(tx, handle).unwrap(), Evented::new(rx, handle).unwrap())
+}
+
+#[test]
+fn runs_multiple_tasks_concurrently() {
+    let mut executor = Executor::new().unwrap();
+    let done = Rc::new(Cell::new(0));
+    for i in 0..8u32 {
+        let (tx, rx) = pair(&executor.handle());
+
+        // The readers are spawned first, so they have to wait for the writers.
+        let done = done.clone();
+        executor.spawn(U32.be().read_from(rx).then(move |result| {
+            assert_eq!(result.ok().unwrap().1, i);
+            done.set(done.get() + 1);
+            Ok(())
+        }));
+        executor.spawn(i.be().write_into(tx).map(|_| ()).map_err(|_| ()));
+    }
+    executor.run().unwrap();
+    assert_eq!(done.get(), 8);
+}
+
+#[test]
+fn transfers_data_over_tcp_loopback() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let client = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+    let (server, _) = listener.accept().unwrap();
+    client.set_nonblocking(true).unwrap();
+    server.set_nonblocking(true).unwrap();
+
+    let mut executor = Executor::new().unwrap();
+    let client = Evented::new(client, &executor.handle()).unwrap();
+    let server = Evented::new(server, &executor.handle()).unwrap();
+
+    // Large enough to fill the socket buffers, so both sides would block.
+    let data = (0..4 * 1024 * 1024).map(|i| i as u8).collect::<Vec<_>>();
+    executor.spawn(data.clone().write_into(client).map(|_| ()).map_err(|_| ()));
+    let future = vec![0; data.len()].read_from(server).map(|(_, v)| v);
+    let received = executor.run_until(future).unwrap().ok().unwrap();
+    assert!(received == data);
+}
+
+#[test]
+fn deregisters_dropped_streams_and_reuses_tokens() {
+    let mut executor = Executor::new().unwrap();
+    let handle = executor.handle();
+
+    // A stream unwrapped by `into_inner` can be registered again.
+    let (tx, rx) = pair(&handle);
+    let rx = Evented::new(rx.into_inner(), &handle).unwrap();
+
+    // New streams take over the tokens of the dropped ones.
+    drop((tx, rx));
+    let (tx, rx) = pair(&handle);
+    let (other_tx, other_rx) = pair(&handle);
+    drop(other_rx);
+
+    // The reader is polled first, so it must be woken via the reused token.
+    let received = Rc::new(Cell::new(None));
+    let result = received.clone();
+    executor.spawn(U8.read_from(rx).then(move |r| {
+        result.set(r.ok().map(|(_, v)| v));
+        Ok(())
+    }));
+    let future = 1u8.write_into(tx).map(|_| ());
+    assert!(executor.run_until(future).unwrap().is_ok());
+    executor.run().unwrap();
+    assert_eq!(received.get(), Some(1));
+    drop(other_tx);
+}